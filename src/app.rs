@@ -1,6 +1,8 @@
 use egui_extras::{Size, StripBuilder};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
+    ops::Range,
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -8,7 +10,7 @@ use std::{
     },
     time::Instant,
 };
-use wif::{Shaft, Warp, Weft, Wif};
+use wif::{Shaft, Wif};
 
 use eframe::{
     egui::{
@@ -17,22 +19,140 @@ use eframe::{
     Storage,
 };
 
-use crate::ewma::Ewma;
+use crate::{
+    calculator::Project,
+    cloth::Sett,
+    colourway::Colourways,
+    dobby::{DobbyLink, DobbySettings},
+    draft::{self, bit, Draft, Mask, MAX_SHAFTS},
+    drawloom::DrawUnits,
+    ewma::Ewma,
+    length::Measurement,
+    loom::{LoomProfile, ShaftOrder, Side},
+    notes::{Note, Target},
+    reminder::Reminder,
+    sections::Sections,
+    shuttle::ShuttleSettings,
+    structure::{analyse, Analysis},
+    tieup::TieupSynthesis,
+    verify::Verification,
+    yarn::YarnStash,
+};
 
+mod calculator;
+mod changes;
+mod cloth;
+mod colourway;
+mod dobby;
+mod drawloom;
+mod floats;
+mod heddles;
+mod length;
+mod motif;
+mod notes;
 mod pedal;
+mod reminder;
+mod sectional;
+mod selvedge;
+mod shuttle;
+mod skeleton;
+mod sleying;
+mod tieup;
+mod unweave;
+mod verify;
+mod weft_runs;
+mod yarn;
 
 pub struct MyApp {
     row: u32,
+    /// Which repeat of the treadling is being woven, from 1.
+    repeat: u32,
     warp: u32,
+    draw_step: u32,
+    heddle_step: u32,
+    sley_step: u32,
+    section_step: u32,
+    /// Turns of the beam wound so far, and the section they were for.
+    turns: u32,
+    turns_section: u32,
+    check_end: u32,
+    verification: Verification,
     average_row_speed: Ewma,
     last_t: Instant,
     wif: Arc<RwLock<Wif>>,
+    /// The open file's sections, for the parts of it `wif` doesn't cover.
+    wif_sections: Arc<RwLock<Sections>>,
     wif_path: Arc<RwLock<Option<PathBuf>>>,
+    /// Why the last draft opened couldn't be used.
+    load_error: Arc<RwLock<Option<String>>>,
     timer_paused: bool,
     pedal_pressed: Arc<AtomicBool>,
+    /// Raised by the dobby when the loom wants the next pick.
+    shed_opened: Arc<AtomicBool>,
     mode: OperationMode,
+    /// The mode on the last frame, to notice coming into drawloom mode.
+    last_mode: OperationMode,
     threading_mode: ThreadingMode,
     threading_batch_size: u32,
+    /// Ends per inch, if the weaver has chosen one rather than using the draft's.
+    epi: Option<f32>,
+    /// Picks per inch, if the weaver has chosen one rather than using the draft's.
+    ppi: Option<f32>,
+    /// Ruler measurements of the cloth, and the one being entered.
+    measurements: Vec<Measurement>,
+    measured_length: f32,
+    /// Why the last measurement entered was turned away.
+    measurement_error: Option<String>,
+    loom: LoomProfile,
+    tieup_synthesis: Option<TieupSynthesis>,
+    /// A tie-up found in the background, waiting to be picked up.
+    tieup_conversion: Arc<RwLock<Option<TieupSynthesis>>>,
+    tieup_converting: bool,
+    show_tieup_window: bool,
+    dobby: DobbySettings,
+    dobby_link: Option<DobbyLink>,
+    dobby_error: Option<String>,
+    show_dobby_window: bool,
+    project: Project,
+    show_calculator_window: bool,
+    yarns: YarnStash,
+    show_yarn_window: bool,
+    reminders: Vec<Reminder>,
+    /// Reminders that have come up and not been acknowledged yet.
+    alerts: Vec<String>,
+    show_reminders_window: bool,
+    /// Whether reminders for where the weaving starts have been checked.
+    reminders_started: bool,
+    /// Seconds spent weaving, going by the row timer.
+    weaving_time: f32,
+    /// Notes for each draft, by the path it was opened from.
+    notes: BTreeMap<String, Vec<Note>>,
+    note_text: String,
+    /// How saving the notes into a WIF went.
+    notes_status: Arc<RwLock<Option<Result<String, String>>>>,
+    show_notes_window: bool,
+    /// The row of the piece (counting repeats) marked as a mistake.
+    mistake: Option<u32>,
+    unweaving: bool,
+    /// Shuttles for each draft, by the path it was opened from.
+    shuttles: BTreeMap<String, ShuttleSettings>,
+    show_shuttles_window: bool,
+    selvedge_hints: bool,
+    show_selvedge_window: bool,
+    float_limit: u32,
+    float_heat_map: bool,
+    float_zoom: f32,
+    show_floats_window: bool,
+    /// The last draft analysed, which way round, and its structure.
+    structure: Option<(Draft, bool, Analysis)>,
+    /// Pixels per inch for the cloth simulation.
+    cloth_zoom: f32,
+    /// The last cloth drawn, and what it was drawn from.
+    cloth: Option<(Draft, Sett, f32, egui::TextureHandle)>,
+    show_cloth_window: bool,
+    /// Colourways for each draft, by the path it was opened from.
+    colourways: BTreeMap<String, Colourways>,
+    show_colourways_window: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,7 +160,14 @@ pub struct MyApp {
 enum OperationMode {
     Liftplan,
     Treadling,
+    Skeleton,
+    Changes,
+    Drawloom,
     Threading,
+    Heddles,
+    Sleying,
+    Verify,
+    Sectional,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -48,6 +175,8 @@ enum OperationMode {
 enum ThreadingMode {
     Continuous,
     Batched,
+    Motif,
+    Auto,
 }
 
 fn save_serialized<T>(storage: &mut dyn Storage, key: &str, value: &T)
@@ -59,6 +188,14 @@ where
     }
 }
 
+/// What's kept for each draft, leaving out drafts that aren't from a file,
+/// as the key wouldn't find the same draft again.
+fn by_file<T: Clone>(by_draft: &BTreeMap<String, T>) -> BTreeMap<String, T> {
+    let mut by_file = by_draft.clone();
+    by_file.remove("");
+    by_file
+}
+
 fn load_serialized<T>(storage: Option<&dyn Storage>, key: &str) -> Option<T>
 where
     T: for<'a> Deserialize<'a>,
@@ -71,49 +208,165 @@ where
 }
 
 impl MyApp {
-    pub fn new(fallback_wif: Wif, cc: &eframe::CreationContext) -> Self {
+    pub fn new(
+        fallback_wif: Wif,
+        fallback_sections: Sections,
+        cc: &eframe::CreationContext,
+    ) -> Self {
         let pedal_pressed = Arc::new(AtomicBool::new(false));
         pedal::watch_pedal(cc.egui_ctx.clone(), Arc::clone(&pedal_pressed));
 
         let row = load_serialized(cc.storage, "row");
         let warp = load_serialized(cc.storage, "warp");
+        let draw_step = load_serialized(cc.storage, "draw_step");
+        let heddle_step = load_serialized(cc.storage, "heddle_step");
+        let sley_step = load_serialized(cc.storage, "sley_step");
+        let section_step = load_serialized(cc.storage, "section_step");
+        let turns = load_serialized(cc.storage, "turns");
+        let check_end = load_serialized(cc.storage, "check_end");
+        let verification = load_serialized(cc.storage, "verification");
         let mode = load_serialized(cc.storage, "mode");
-        let wif_path = load_serialized(cc.storage, "wif_path");
+        let wif_path: Option<PathBuf> = load_serialized(cc.storage, "wif_path");
         let wif = wif_path.as_deref().and_then(|path| {
-            std::fs::read_to_string(path)
-                .ok()
-                .and_then(|contents| wif::parse(&contents).ok())
+            std::fs::read_to_string(path).ok().and_then(|contents| {
+                let wif = wif::parse(&contents).ok()?;
+                Some((wif, Sections::parse(&contents)))
+            })
         });
+        let load_error = wif.as_ref().and_then(|(wif, _)| draft::check(wif).err());
+        let (wif, wif_path) = if load_error.is_some() {
+            (None, None)
+        } else {
+            (wif, wif_path)
+        };
+        let wif_sections = wif.unwrap_or((fallback_wif, fallback_sections));
         let average_row_speed = load_serialized(cc.storage, "average_row_speed");
         let threading_mode = load_serialized(cc.storage, "threading_mode");
         let threading_batch_size = load_serialized(cc.storage, "threading_batch_size");
+        let epi = load_serialized(cc.storage, "epi");
+        let ppi = load_serialized(cc.storage, "ppi");
+        let measurements = load_serialized(cc.storage, "measurements");
+        let project = load_serialized(cc.storage, "project");
+        let yarns = load_serialized(cc.storage, "yarns");
+        let repeat = load_serialized(cc.storage, "repeat");
+        let reminders = load_serialized(cc.storage, "reminders");
+        let weaving_time = load_serialized(cc.storage, "weaving_time");
+        let notes = load_serialized(cc.storage, "notes");
+        let mistake = load_serialized(cc.storage, "mistake");
+        let unweaving = load_serialized(cc.storage, "unweaving");
+        let shuttles = load_serialized(cc.storage, "shuttles");
+        let selvedge_hints = load_serialized(cc.storage, "selvedge_hints");
+        let float_limit = load_serialized(cc.storage, "float_limit");
+        let cloth_zoom = load_serialized(cc.storage, "cloth_zoom");
+        let colourways = load_serialized(cc.storage, "colourways");
+        let loom = load_serialized(cc.storage, "loom");
+        let dobby = load_serialized(cc.storage, "dobby");
 
         Self {
             row: row.unwrap_or(1),
+            repeat: repeat.unwrap_or(1),
             warp: warp.unwrap_or(1),
+            draw_step: draw_step.unwrap_or(1),
+            heddle_step: heddle_step.unwrap_or(1),
+            sley_step: sley_step.unwrap_or(1),
+            section_step: section_step.unwrap_or(1),
+            turns: turns.unwrap_or_default(),
+            turns_section: section_step.unwrap_or(1),
+            check_end: check_end.unwrap_or(1),
+            verification: verification.unwrap_or_default(),
             average_row_speed: average_row_speed.unwrap_or_else(|| Ewma::new(0.1)),
             last_t: Instant::now(),
-            wif: Arc::new(RwLock::new(wif.unwrap_or(fallback_wif))),
+            wif: Arc::new(RwLock::new(wif_sections.0)),
+            wif_sections: Arc::new(RwLock::new(wif_sections.1)),
             wif_path: Arc::new(RwLock::new(wif_path)),
+            load_error: Arc::new(RwLock::new(load_error)),
             timer_paused: false,
             pedal_pressed,
+            shed_opened: Arc::new(AtomicBool::new(false)),
             mode: mode.unwrap_or(OperationMode::Liftplan),
+            last_mode: mode.unwrap_or(OperationMode::Liftplan),
             threading_mode: threading_mode.unwrap_or(ThreadingMode::Continuous),
             threading_batch_size: threading_batch_size.unwrap_or(8),
+            epi: epi.unwrap_or_default(),
+            ppi: ppi.unwrap_or_default(),
+            measurements: measurements.unwrap_or_default(),
+            measured_length: 0.,
+            measurement_error: None,
+            loom: loom.unwrap_or_default(),
+            tieup_synthesis: None,
+            tieup_conversion: Arc::new(RwLock::new(None)),
+            tieup_converting: false,
+            show_tieup_window: false,
+            dobby: dobby.unwrap_or_default(),
+            dobby_link: None,
+            dobby_error: None,
+            show_dobby_window: false,
+            project: project.unwrap_or_default(),
+            show_calculator_window: false,
+            yarns: yarns.unwrap_or_default(),
+            show_yarn_window: false,
+            reminders: reminders.unwrap_or_default(),
+            alerts: vec![],
+            show_reminders_window: false,
+            reminders_started: false,
+            weaving_time: weaving_time.unwrap_or_default(),
+            notes: notes.unwrap_or_default(),
+            note_text: String::new(),
+            notes_status: Arc::new(RwLock::new(None)),
+            show_notes_window: false,
+            mistake: mistake.unwrap_or_default(),
+            unweaving: unweaving.unwrap_or_default(),
+            shuttles: shuttles.unwrap_or_default(),
+            show_shuttles_window: false,
+            selvedge_hints: selvedge_hints.unwrap_or_default(),
+            show_selvedge_window: false,
+            float_limit: float_limit.unwrap_or(7),
+            float_heat_map: false,
+            float_zoom: 6.,
+            show_floats_window: false,
+            structure: None,
+            cloth_zoom: cloth_zoom.unwrap_or(100.),
+            cloth: None,
+            show_cloth_window: false,
+            colourways: colourways.unwrap_or_default(),
+            show_colourways_window: false,
         }
     }
 
     fn row_counter(&mut self, ui: &mut egui::Ui, last_row: u32) {
         ui.horizontal_top(|ui| {
             let drag_widget = match self.mode {
-                OperationMode::Liftplan | OperationMode::Treadling => {
+                OperationMode::Liftplan
+                | OperationMode::Treadling
+                | OperationMode::Skeleton
+                | OperationMode::Changes => {
                     ui.label("Row ");
                     DragValue::new(&mut self.row)
                 }
+                OperationMode::Drawloom => {
+                    ui.label("Step ");
+                    DragValue::new(&mut self.draw_step)
+                }
                 OperationMode::Threading => {
                     ui.label("Thread ");
                     DragValue::new(&mut self.warp)
                 }
+                OperationMode::Heddles => {
+                    ui.label("Step ");
+                    DragValue::new(&mut self.heddle_step)
+                }
+                OperationMode::Sleying => {
+                    ui.label("Dent ");
+                    DragValue::new(&mut self.sley_step)
+                }
+                OperationMode::Verify => {
+                    ui.label("End ");
+                    DragValue::new(&mut self.check_end)
+                }
+                OperationMode::Sectional => {
+                    ui.label("Section ");
+                    DragValue::new(&mut self.section_step)
+                }
             };
             let drag_widget = drag_widget
                 .range(1..=last_row)
@@ -124,49 +377,78 @@ impl MyApp {
             }
             ui.label(format!("/{last_row}"));
         });
+        if self.weaving_mode() {
+            ui.horizontal_top(|ui| {
+                ui.label("Repeat ");
+                ui.add(DragValue::new(&mut self.repeat).range(1..=9999));
+            });
+        }
     }
 
     fn control_buttons(&mut self, ui: &mut egui::Ui, pedal_pressed: bool, last_row: u32) {
-        let label = if self.threading_mode() {
-            "Next thread"
-        } else {
-            "Next row"
-        };
-        let next_row = Button::new(label).min_size([64., 64.].into());
-        let var = if self.threading_mode() {
-            &mut self.warp
+        let unweaving = self.unweaving && self.can_unweave();
+        let (next_label, prev_label) = if unweaving {
+            ("Unweave row", "Prev row")
         } else {
-            &mut self.row
-        };
-        if ui.add(next_row).clicked() || pedal_pressed {
-            *var += 1;
-            if *var > last_row {
-                *var = 1;
-            }
-            if !self.timer_paused {
-                self.average_row_speed
-                    .record(self.last_t.elapsed().as_secs_f32());
+            match self.mode {
+                OperationMode::Threading | OperationMode::Verify => ("Next thread", "Prev thread"),
+                OperationMode::Drawloom | OperationMode::Heddles => ("Next step", "Prev step"),
+                OperationMode::Sleying => ("Next dent", "Prev dent"),
+                OperationMode::Sectional => ("Next section", "Prev section"),
+                _ => ("Next row", "Prev row"),
             }
+        };
+        let next_row = Button::new(next_label).min_size([64., 64.].into());
+        let next = ui.add(next_row).clicked() || pedal_pressed;
+        let prev = ui.button(prev_label).clicked();
+        if !next && !prev {
+            return;
+        }
+        if unweaving && next {
+            self.unweave_row(last_row);
             self.last_t = Instant::now();
+            return;
         }
 
-        let label = if self.mode == OperationMode::Threading {
-            "Prev thread"
-        } else {
-            "Prev row"
+        // Threading from the far end of the warp counts ends downwards.
+        let weaving = self.weaving_mode();
+        let forwards = !matches!(self.mode, OperationMode::Threading | OperationMode::Verify)
+            || self.loom.orientation.threads_ascending();
+        if next && self.mode == OperationMode::Verify {
+            // Moving on from an end confirms it, unless it's been corrected.
+            self.verification.confirm(self.check_end);
+        }
+        let var = match self.mode {
+            OperationMode::Threading => &mut self.warp,
+            OperationMode::Drawloom => &mut self.draw_step,
+            OperationMode::Heddles => &mut self.heddle_step,
+            OperationMode::Sleying => &mut self.sley_step,
+            OperationMode::Verify => &mut self.check_end,
+            OperationMode::Sectional => &mut self.section_step,
+            _ => &mut self.row,
         };
-        if ui.button(label).clicked() {
-            if let Some(new_row) = var.checked_sub(1) {
-                if new_row == 0 {
-                    *var = last_row;
-                } else {
-                    *var = new_row;
-                }
-            } else {
-                *var = last_row;
+        let before = *var;
+        if next == forwards {
+            count_up(var, last_row);
+        } else {
+            count_down(var, last_row);
+        }
+        // Wrapping around the treadling starts another repeat of it.
+        let after = *var;
+        if weaving && next && after < before {
+            self.repeat += 1;
+        } else if weaving && prev && after > before {
+            self.repeat = self.repeat.saturating_sub(1).max(1);
+        }
+
+        if next && !self.timer_paused {
+            let elapsed = self.last_t.elapsed().as_secs_f32();
+            self.average_row_speed.record(elapsed);
+            if self.weaving_mode() {
+                self.weaving_time += elapsed;
             }
-            self.last_t = Instant::now();
         }
+        self.last_t = Instant::now();
     }
 
     fn menus(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
@@ -175,7 +457,9 @@ impl MyApp {
                 if ui.button("Open").clicked() {
                     let ctx = ctx.clone();
                     let wif = self.wif.clone();
+                    let wif_sections = self.wif_sections.clone();
                     let wif_path = self.wif_path.clone();
+                    let load_error = self.load_error.clone();
                     std::thread::spawn(move || {
                         if let Some(fname) = rfd::FileDialog::new()
                             .add_filter("WIF", &["wif"])
@@ -187,11 +471,20 @@ impl MyApp {
                                     Err(e) => {
                                         eprintln!("Error parsing WIF file: {e}");
                                     }
-                                    Ok(parsed) => {
-                                        *wif.write().unwrap() = parsed;
-                                        *wif_path.write().unwrap() = Some(fname);
-                                        ctx.request_repaint();
-                                    }
+                                    Ok(parsed) => match draft::check(&parsed) {
+                                        Err(e) => {
+                                            *load_error.write().unwrap() = Some(e);
+                                            ctx.request_repaint();
+                                        }
+                                        Ok(()) => {
+                                            *wif.write().unwrap() = parsed;
+                                            *wif_sections.write().unwrap() =
+                                                Sections::parse(&contents);
+                                            *wif_path.write().unwrap() = Some(fname);
+                                            *load_error.write().unwrap() = None;
+                                            ctx.request_repaint();
+                                        }
+                                    },
                                 },
                                 Err(e) => {
                                     eprintln!("Error opening file {}: {e}", fname.display());
@@ -212,21 +505,169 @@ impl MyApp {
                     || ui
                         .radio_value(&mut self.mode, OperationMode::Treadling, "Treadling")
                         .clicked()
+                    || ui
+                        .radio_value(
+                            &mut self.mode,
+                            OperationMode::Skeleton,
+                            "Skeleton treadling",
+                        )
+                        .clicked()
+                    || ui
+                        .radio_value(&mut self.mode, OperationMode::Changes, "Lever changes")
+                        .clicked()
+                    || ui
+                        .radio_value(&mut self.mode, OperationMode::Drawloom, "Drawloom")
+                        .clicked()
                     || ui
                         .radio_value(&mut self.mode, OperationMode::Threading, "Threading")
                         .clicked()
+                    || ui
+                        .radio_value(&mut self.mode, OperationMode::Heddles, "Heddles")
+                        .clicked()
+                    || ui
+                        .radio_value(&mut self.mode, OperationMode::Sleying, "Sleying")
+                        .clicked()
+                    || ui
+                        .radio_value(&mut self.mode, OperationMode::Verify, "Check threading")
+                        .clicked()
+                    || ui
+                        .radio_value(
+                            &mut self.mode,
+                            OperationMode::Sectional,
+                            "Sectional warping",
+                        )
+                        .clicked()
                 {
                     ui.close_menu();
                 }
             });
+            ui.menu_button("Loom", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Treadles");
+                    ui.add(DragValue::new(&mut self.loom.treadles).range(1..=64u32));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Heddles per shaft");
+                    ui.add(DragValue::new(&mut self.loom.heddles_per_shaft));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Reed");
+                    ui.add(DragValue::new(&mut self.loom.dents_per_inch).range(1..=100u32));
+                    ui.label("dents per inch,");
+                    ui.add(
+                        DragValue::new(&mut self.loom.reed_width)
+                            .range(1.0..=200.0)
+                            .speed(0.5),
+                    );
+                    ui.label("inches wide");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Sectional beam");
+                    ui.add(
+                        DragValue::new(&mut self.loom.section_width)
+                            .range(0.25..=12.0)
+                            .speed(0.05)
+                            .suffix("\" sections,"),
+                    );
+                    ui.add(
+                        DragValue::new(&mut self.loom.beam_circumference)
+                            .range(1.0..=120.0)
+                            .speed(0.5)
+                            .suffix("\" a turn"),
+                    );
+                });
+                ui.separator();
+                let orientation = &mut self.loom.orientation;
+                ui.horizontal(|ui| {
+                    ui.label("End 1 is on the");
+                    ui.radio_value(&mut orientation.end_one, Side::Left, "left");
+                    ui.radio_value(&mut orientation.end_one, Side::Right, "right");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Shaft 1 is at the");
+                    ui.radio_value(&mut orientation.shafts, ShaftOrder::OneAtFront, "front");
+                    ui.radio_value(&mut orientation.shafts, ShaftOrder::OneAtBack, "back");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Thread from the");
+                    ui.radio_value(&mut orientation.start, Side::Left, "left");
+                    ui.radio_value(&mut orientation.start, Side::Right, "right");
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Drawloom ground shafts");
+                    ui.add(DragValue::new(&mut self.loom.ground_shafts).range(0..=64u32));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Pattern drawn with");
+                    ui.radio_value(&mut self.loom.draw_units, DrawUnits::Shafts, "shafts");
+                    ui.radio_value(&mut self.loom.draw_units, DrawUnits::Cords, "cords");
+                });
+                if self.loom.draw_units == DrawUnits::Cords {
+                    ui.label(format!(
+                        "Drafts with more than {MAX_SHAFTS} cords and ground shafts together \
+                         aren't supported."
+                    ));
+                }
+            });
+            ui.menu_button("Tools", |ui| {
+                if ui.button("Liftplan to treadling").clicked() {
+                    self.show_tieup_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Dobby output").clicked() {
+                    self.show_dobby_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Warp calculator").clicked() {
+                    self.show_calculator_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Yarn stash").clicked() {
+                    self.show_yarn_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Reminders").clicked() {
+                    self.show_reminders_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Notes").clicked() {
+                    self.show_notes_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Shuttles").clicked() {
+                    self.show_shuttles_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Selvedges").clicked() {
+                    self.show_selvedge_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Float check").clicked() {
+                    self.show_floats_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Cloth simulation").clicked() {
+                    self.show_cloth_window = true;
+                    ui.close_menu();
+                }
+                if ui.button("Colourways").clicked() {
+                    self.show_colourways_window = true;
+                    ui.close_menu();
+                }
+            });
         });
     }
 
-    fn timings(&mut self, ui: &mut egui::Ui, last_row: u32) {
-        let row = if self.threading_mode() {
-            self.warp
-        } else {
-            self.row
+    fn timings(&mut self, ui: &mut egui::Ui, draft: &Draft, last_row: u32) {
+        let row = match self.mode {
+            OperationMode::Threading => self.warp,
+            OperationMode::Drawloom => self.draw_step,
+            OperationMode::Heddles => self.heddle_step,
+            OperationMode::Sleying => self.sley_step,
+            OperationMode::Verify => self.check_end,
+            OperationMode::Sectional => self.section_step,
+            _ => self.row,
         };
 
         ui.label(format!(
@@ -240,19 +681,140 @@ impl MyApp {
             let s = eta % 60;
             ui.label(format!("Time estimate:\n{h}h {m:02}m {s:02}s"));
         }
+        if self.weaving_mode() {
+            self.woven_length(ui, draft);
+        }
         if ui.button("Reset timer").clicked() {
             self.average_row_speed.reset();
         }
     }
 
+    /// Notes, colourways, shuttles and yarn assignments belong to the draft
+    /// they were made for, keyed by its path. Drafts that aren't from a file get an
+    /// empty key, and what's kept for them isn't saved.
+    fn draft_key(&self) -> String {
+        self.wif_path
+            .read()
+            .unwrap()
+            .as_deref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Treadles pressed on `pick`, preferring a tie-up converted from the liftplan.
+    fn treadles_pressed(&self, draft: &Draft, pick: u32) -> Mask {
+        match &self.tieup_synthesis {
+            Some(synthesis) => synthesis.treadling(pick),
+            None => draft.treadling(pick),
+        }
+    }
+
+    /// Shafts lifted by the treadles pressed on `pick`, going by the tie-up
+    /// they're pressed against, or the liftplan if there's no tie-up.
+    fn treadled_lift(&self, draft: &Draft, pick: u32) -> Mask {
+        let tieup = match &self.tieup_synthesis {
+            Some(synthesis) => &synthesis.tieup[..],
+            None => draft.tieup(),
+        };
+        if tieup.is_empty() {
+            draft.lift(pick)
+        } else {
+            draft::tied_lift(tieup, self.treadles_pressed(draft, pick))
+        }
+    }
+
+    /// What kind of cloth the draft makes, worked out again only when the
+    /// draft changes.
+    fn show_structure(&mut self, ui: &mut egui::Ui, draft: &Draft) {
+        let end_one_on_left = self.loom.orientation.end_one == Side::Left;
+        let stale = !matches!(
+            &self.structure,
+            Some((analysed, left, _)) if analysed == draft && *left == end_one_on_left
+        );
+        if stale {
+            let analysis = analyse(draft, end_one_on_left);
+            self.structure = Some((draft.clone(), end_one_on_left, analysis));
+        }
+        let Some((_, _, analysis)) = &self.structure else {
+            return;
+        };
+        let mut text = format!(
+            "Structure: {}, {}, {} sheds, repeat {}×{}",
+            analysis.structure,
+            analysis.facing(),
+            analysis.sheds,
+            analysis.repeat.0,
+            analysis.repeat.1
+        );
+        if let Some(balance) = analysis.balance {
+            if (balance - 1.).abs() < 0.1 {
+                text.push_str(", balanced sett");
+            } else {
+                text.push_str(&format!(", {balance:.2} ends per pick"));
+            }
+        }
+        ui.label(text);
+    }
+
+    fn treadle_count(&self, draft: &Draft) -> u32 {
+        match &self.tieup_synthesis {
+            Some(synthesis) => synthesis.treadles(),
+            None => draft.treadles,
+        }
+    }
+
     fn threading_mode(&mut self) -> bool {
         self.mode == OperationMode::Threading
     }
 
-    fn show_liftplan(&mut self, ui: &mut egui::Ui, wif: Wif, shafts: u32, last_row: u32) {
-        let lift_plan = wif.liftplan.as_ref();
-        let treadling = wif.treadling.as_ref();
+    fn weaving_mode(&self) -> bool {
+        !matches!(
+            self.mode,
+            OperationMode::Threading
+                | OperationMode::Heddles
+                | OperationMode::Sleying
+                | OperationMode::Verify
+                | OperationMode::Sectional
+        )
+    }
+
+    /// Ends per inch: the weaver's choice if they've made one, otherwise the draft's.
+    fn epi(&self, draft: &Draft) -> f32 {
+        self.epi
+            .or(draft.warp_spacing.map(|spacing| 1. / spacing))
+            .unwrap_or(24.)
+    }
+
+    /// Picks per inch: the weaver's choice if they've made one, otherwise the
+    /// draft's, or a balanced weave if the draft doesn't say.
+    fn ppi(&self, draft: &Draft) -> f32 {
+        self.ppi
+            .or(draft.weft_spacing.map(|spacing| 1. / spacing))
+            .unwrap_or_else(|| self.epi(draft))
+    }
+
+    fn show_liftplan(&mut self, ui: &mut egui::Ui, draft: &Draft, shafts: u32, last_row: u32) {
         ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        // Leave room beside the rows for notes, if there are any.
+        let noted = self
+            .notes()
+            .iter()
+            .any(|note| matches!(note.target, Target::Row(_)));
+        let columns = if noted { shafts + 3 } else { shafts + 1 };
+        self.unweaving_banner(ui, last_row);
+        if !self.unweaving {
+            self.shuttle_banner(ui, draft);
+            self.weft_runs(ui, draft);
+            self.selvedge_hint(ui, draft);
+        }
+        // Unweaving works back through the woven rows, down to the mistake.
+        let to_unweave = self.rows_to_unweave(last_row);
+        let direction = if to_unweave.is_some() { -1 } else { 1 };
+        // While unweaving, the row to take out is the last one woven.
+        let mut current = self.row;
+        if to_unweave.is_some() {
+            count_down(&mut current, last_row);
+        }
         StripBuilder::new(ui)
             .cell_layout(Layout::centered_and_justified(egui::Direction::LeftToRight))
             .size(Size::exact(20.))
@@ -266,75 +828,119 @@ impl MyApp {
             .size(Size::exact(20.))
             .vertical(|mut strip| {
                 for offset in [-2, -1, 0, 1, 2, 3, 4, 5, 6] {
-                    let row_num = self.row as i32 + offset;
-                    if row_num <= 0 || row_num > last_row as i32 {
+                    let row_num = current as i32 + offset * direction;
+                    let past_mistake = to_unweave.is_some_and(|rows| offset >= rows as i32);
+                    if row_num <= 0 || row_num > last_row as i32 || past_mistake {
                         strip.empty();
                         continue;
                     }
 
                     let row_num = row_num as u32;
+                    // Rows the WIF leaves out are left out here too.
+                    let given = match (self.mode, &self.tieup_synthesis) {
+                        (OperationMode::Liftplan, _) | (_, Some(_)) => draft.has_lift(row_num),
+                        _ => draft.has_treadling(row_num),
+                    };
+                    if !given {
+                        strip.empty();
+                        continue;
+                    }
                     let row = if self.mode == OperationMode::Liftplan {
-                        lift_plan
-                            .and_then(|lift_plan| lift_plan.get(&Weft::from(row_num)))
-                            .cloned()
+                        draft.lift(row_num)
                     } else {
-                        treadling.and_then(|treadling| {
-                            treadling
-                                .get(&Weft::from(row_num))
-                                .map(|t| t.iter().map(|t| Shaft::from(t.0)).collect())
-                        })
+                        self.treadles_pressed(draft, row_num)
                     };
-                    if let Some(row) = row {
-                        strip.strip(|sb| {
-                            sb.size(Size::exact(20.))
-                                .sizes(Size::relative(1. / ((shafts + 1) as f32)), shafts as usize)
-                                .horizontal(|mut strip| {
-                                    let color = wif.weft_color_u8(row_num).unwrap_or_default();
-                                    let color = Color32::from_rgb(color[0], color[1], color[2]);
+                    strip.strip(|sb| {
+                        sb.size(Size::exact(20.))
+                            .sizes(Size::relative(1. / columns as f32), shafts as usize)
+                            .size(Size::remainder())
+                            .horizontal(|mut strip| {
+                                let color = draft.weft_colour(row_num);
+                                let shuttle = self.shuttle_letter(draft, row_num);
+                                let color = Color32::from_rgb(color[0], color[1], color[2]);
+                                strip.cell(|ui| match shuttle {
+                                    Some(shuttle) => labelled_colour_block(
+                                        ui,
+                                        color,
+                                        &shuttle.to_string(),
+                                        offset == 0,
+                                    ),
+                                    None => colour_block(ui, color, offset == 0),
+                                });
+
+                                let order = if self.mode == OperationMode::Liftplan {
+                                    self.loom.orientation.shaft_order(shafts)
+                                } else {
+                                    (1..=shafts).collect()
+                                };
+                                for shaft in order {
                                     strip.cell(|ui| {
-                                        colour_block(ui, color, offset == 0);
+                                        text_block(
+                                            ui,
+                                            RichText::new(format!("{shaft}"))
+                                                .size(row_text_size(offset)),
+                                            offset == 0,
+                                            row & bit(shaft) != 0,
+                                        );
                                     });
-
-                                    for shaft in 1..=shafts {
-                                        strip.cell(|ui| {
-                                            text_block(
-                                                ui,
-                                                RichText::new(format!("{shaft}")).size(
-                                                    if offset == 0 {
-                                                        64.
-                                                    } else if offset.abs() == 1 {
-                                                        32.
-                                                    } else {
-                                                        16.
-                                                    },
-                                                ),
-                                                offset == 0,
-                                                row.contains(&Shaft::from(shaft)),
-                                            );
-                                        });
-                                    }
-                                });
-                        });
-                    } else {
-                        strip.empty();
-                    }
+                                }
+                                if noted {
+                                    let notes: Vec<_> = self
+                                        .notes_on(Target::Row(row_num))
+                                        .map(|note| note.to_string())
+                                        .collect();
+                                    strip.cell(|ui| {
+                                        ui.label(
+                                            RichText::new(notes.join("\n"))
+                                                .size(row_text_size(offset) / 2.),
+                                        );
+                                    });
+                                }
+                            });
+                    });
                 }
             });
     }
 
-    fn show_threading(&mut self, ui: &mut egui::Ui, wif: Wif, shaft_count: u32, last_row: u32) {
+    fn show_threading(
+        &mut self,
+        ui: &mut egui::Ui,
+        wif: Wif,
+        draft: &Draft,
+        shaft_count: u32,
+        last_row: u32,
+        batches: &[Range<u32>],
+    ) {
         ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        let orientation = self.loom.orientation;
         let cols = self.threading_batch_size;
-        let range = if self.threading_mode == ThreadingMode::Continuous {
-            (self.warp as i32 - 2..).take(cols as usize)
-        } else {
-            let start = ((self.warp - 1) / self.threading_batch_size * self.threading_batch_size)
-                as i32
-                + 1;
-            (start..).take(cols as usize)
+        // Work in threading order, then lay the ends out from the side we start on.
+        let step = orientation.step_at_end(self.warp, last_row);
+        let range = match self.threading_mode {
+            ThreadingMode::Continuous => step - 2..step - 2 + cols as i32,
+            ThreadingMode::Auto => {
+                let batch = batches
+                    .iter()
+                    .position(|batch| batch.contains(&(step as u32)))
+                    .unwrap_or(0);
+                let steps = batches.get(batch).cloned().unwrap_or(1..1);
+                ui.label(format!(
+                    "Batch {} of {}: {} ends",
+                    batch + 1,
+                    batches.len(),
+                    steps.len()
+                ));
+                steps.start as i32..steps.end as i32
+            }
+            _ => {
+                let start = ((step.max(1) as u32 - 1) / self.threading_batch_size
+                    * self.threading_batch_size) as i32
+                    + 1;
+                start..start + cols as i32
+            }
         };
-        let threading: Vec<_> = range
-            .clone()
+        let mut threading: Vec<_> = range
+            .map(|step| orientation.end_at_step(step, last_row))
             .map(|thread| {
                 (
                     thread,
@@ -348,6 +954,11 @@ impl MyApp {
                 )
             })
             .collect();
+        // Smart batches vary in length, so keep the columns the same width.
+        threading.resize(cols as usize, (0, None));
+        if orientation.start == Side::Right {
+            threading.reverse();
+        }
         StripBuilder::new(ui)
             .cell_layout(Layout::centered_and_justified(egui::Direction::LeftToRight))
             .sizes(Size::relative(1. / ((cols + 1) as f32)), cols as usize)
@@ -363,16 +974,13 @@ impl MyApp {
                                     shaft_count as usize,
                                 )
                                 .vertical(|mut strip| {
-                                    let colour = wif
-                                        .warp_color_u8(Warp::from(thread as u32))
-                                        .unwrap_or_default();
-
+                                    let colour = draft.warp_colour(thread as u32);
                                     let colour = Color32::from_rgb(colour[0], colour[1], colour[2]);
                                     strip.cell(|ui| {
                                         colour_block(ui, colour, thread == self.warp as i32)
                                     });
 
-                                    for shaft in 1..=shaft_count {
+                                    for shaft in orientation.shaft_order(shaft_count) {
                                         if let Some(shafts) = shafts {
                                             if shafts.contains(&Shaft::from(shaft)) {
                                                 strip.cell(|ui| {
@@ -403,37 +1011,149 @@ impl eframe::App for MyApp {
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         save_serialized(storage, "row", &self.row);
         save_serialized(storage, "warp", &self.warp);
+        save_serialized(storage, "draw_step", &self.draw_step);
+        save_serialized(storage, "heddle_step", &self.heddle_step);
+        save_serialized(storage, "sley_step", &self.sley_step);
+        save_serialized(storage, "section_step", &self.section_step);
+        save_serialized(storage, "turns", &self.turns);
+        save_serialized(storage, "check_end", &self.check_end);
+        save_serialized(storage, "verification", &self.verification);
         save_serialized(storage, "mode", &self.mode);
         save_serialized(storage, "wif_path", &self.wif_path);
         save_serialized(storage, "average_row_speed", &self.average_row_speed);
         save_serialized(storage, "threading_mode", &self.threading_mode);
         save_serialized(storage, "threading_batch_size", &self.threading_batch_size);
+        save_serialized(storage, "epi", &self.epi);
+        save_serialized(storage, "ppi", &self.ppi);
+        save_serialized(storage, "measurements", &self.measurements);
+        save_serialized(storage, "project", &self.project);
+        let mut yarns = self.yarns.clone();
+        yarns.assignments = by_file(&yarns.assignments);
+        save_serialized(storage, "yarns", &yarns);
+        save_serialized(storage, "repeat", &self.repeat);
+        save_serialized(storage, "reminders", &self.reminders);
+        save_serialized(storage, "weaving_time", &self.weaving_time);
+        save_serialized(storage, "notes", &by_file(&self.notes));
+        save_serialized(storage, "mistake", &self.mistake);
+        save_serialized(storage, "unweaving", &self.unweaving);
+        save_serialized(storage, "shuttles", &by_file(&self.shuttles));
+        save_serialized(storage, "selvedge_hints", &self.selvedge_hints);
+        save_serialized(storage, "float_limit", &self.float_limit);
+        save_serialized(storage, "cloth_zoom", &self.cloth_zoom);
+        save_serialized(storage, "colourways", &by_file(&self.colourways));
+        save_serialized(storage, "loom", &self.loom);
+        save_serialized(storage, "dobby", &self.dobby);
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.set_zoom_factor(1.5);
         let wif = self.wif.read().unwrap().clone();
+        let original = Draft::from_wif(&wif, &self.wif_sections.read().unwrap());
+        // Everything from here on sees the draft in the colourway being woven.
+        let draft = self.colourways().apply(&original);
+        if self
+            .tieup_synthesis
+            .as_ref()
+            .is_some_and(|synthesis| synthesis.liftplan != draft.liftplan())
+        {
+            self.tieup_synthesis = None;
+        }
+        if !self.verification.is_for(&draft) {
+            self.verification = Verification::new(&draft);
+        }
         let pedal_pressed = if self.pedal_pressed.load(Ordering::Acquire) {
             self.pedal_pressed.store(false, Ordering::Relaxed);
             true
         } else {
             false
         };
+        // While a reminder is up, the pedal acknowledges it rather than moving on.
+        let pedal_pressed = if pedal_pressed && !self.alerts.is_empty() {
+            self.acknowledge_alert();
+            false
+        } else {
+            pedal_pressed
+        };
+        // The loom moves on by itself, so it never acknowledges a reminder.
+        let pedal_pressed = pedal_pressed || self.shed_opened.swap(false, Ordering::AcqRel);
 
+        let motif_mode = self.threading_mode() && self.threading_mode == ThreadingMode::Motif;
+        let motifs = if self.threading_mode()
+            && matches!(
+                self.threading_mode,
+                ThreadingMode::Motif | ThreadingMode::Auto
+            ) {
+            crate::motif::motifs(&self.threading_steps(&draft))
+        } else {
+            vec![]
+        };
+        let batches = if self.threading_mode() && self.threading_mode == ThreadingMode::Auto {
+            crate::motif::batches(
+                &motifs,
+                &self.warp_colour_steps(&draft),
+                self.threading_batch_size,
+            )
+        } else {
+            vec![]
+        };
+        let denting = (self.mode == OperationMode::Sleying).then(|| {
+            crate::sleying::plan(
+                draft.ends(),
+                self.epi(&draft),
+                self.loom.dents_per_inch,
+                self.loom.reed_width,
+            )
+        });
+        let sections = (self.mode == OperationMode::Sectional).then(|| self.sectional_plan(&draft));
+        let draw_steps = if self.mode == OperationMode::Drawloom {
+            crate::drawloom::sequence(&draft, self.loom.ground_shafts)
+        } else {
+            vec![]
+        };
+        // Coming into drawloom mode, pick up at the row woven in other modes.
+        if self.mode == OperationMode::Drawloom && self.last_mode != OperationMode::Drawloom {
+            self.draw_step = crate::drawloom::step_for_pick(&draw_steps, self.row);
+        }
+        self.last_mode = self.mode;
         let last_row = if self.threading_mode() {
             wif.warp.as_ref().map(|wefts| wefts.threads).unwrap_or(1)
+        } else if self.mode == OperationMode::Drawloom {
+            (draw_steps.len() as u32).max(1)
+        } else if self.mode == OperationMode::Heddles {
+            // Each shaft is done in two steps, one for each side of the centre.
+            draft.shafts * 2
+        } else if let Some(denting) = &denting {
+            (denting.dents.len() as u32).max(1)
+        } else if self.mode == OperationMode::Verify {
+            draft.ends().max(1)
+        } else if let Some(sections) = &sections {
+            (sections.sections.len() as u32).max(1)
         } else {
             wif.weft.as_ref().map(|wefts| wefts.threads).unwrap_or(1)
         };
         let shafts = if self.mode == OperationMode::Liftplan || self.threading_mode() {
             wif.shafts().unwrap_or(4)
         } else {
-            wif.treadles().unwrap_or(6)
+            self.treadle_count(&draft)
         };
 
         egui::TopBottomPanel::top("menubar").show(ctx, |ui| {
             self.menus(ui, ctx);
         });
+        self.tieup_window(ctx, &draft);
+        self.dobby_window(ctx);
+        self.calculator_window(ctx, &draft);
+        self.yarn_window(ctx, &draft);
+        let yarn_usage = self.yarn_usage(&draft);
+        self.reminders_window(ctx);
+        self.notes_window(ctx, &draw_steps);
+        self.shuttles_window(ctx, &draft);
+        self.selvedge_window(ctx, &draft);
+        self.floats_window(ctx, &draft);
+        self.cloth_window(ctx, &draft);
+        self.colourways_window(ctx, &original);
+        self.show_alerts(ctx);
+        let mut before = None;
         egui::SidePanel::left("left panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
@@ -446,14 +1166,31 @@ impl eframe::App for MyApp {
                             "Continuous",
                         );
                         ui.radio_value(&mut self.threading_mode, ThreadingMode::Batched, "Batched");
+                        ui.radio_value(
+                            &mut self.threading_mode,
+                            ThreadingMode::Auto,
+                            "Smart batches",
+                        );
+                        ui.radio_value(&mut self.threading_mode, ThreadingMode::Motif, "By motif");
 
                         let drag_value = DragValue::new(&mut self.threading_batch_size)
                             .range(1..=25u32)
                             .update_while_editing(false);
                         ui.add(drag_value);
                     }
+                    before = Some(self.progress(&draft));
                     self.control_buttons(ui, pedal_pressed, last_row);
-                    self.timings(ui, last_row);
+                    if motif_mode {
+                        self.motif_buttons(ui, &motifs, last_row);
+                    }
+                    self.timings(ui, &draft, last_row);
+                    if self.weaving_mode() {
+                        self.yarn_warnings(ui, &yarn_usage, &draft);
+                    }
+
+                    if self.can_unweave() {
+                        self.mistake_controls(ui, &draft);
+                    }
 
                     if !self.timer_paused && ui.button("Pause timer").clicked() {
                         self.timer_paused = true;
@@ -465,8 +1202,30 @@ impl eframe::App for MyApp {
                 });
             });
         });
+        if let Some(step) = (self.draw_step as usize)
+            .checked_sub(1)
+            .and_then(|i| draw_steps.get(i))
+        {
+            // Keep the row in step, so switching back to another view picks up where we are.
+            self.row = step.pick();
+        }
+        // Only now has the row caught up with any step on the drawloom.
+        if self.weaving_mode() {
+            let after = self.progress(&draft);
+            if !self.reminders_started {
+                self.reminders_started = true;
+                if after.picks == 0 {
+                    self.check_reminders(None, &after);
+                }
+            } else if let Some(before) = before.filter(|before| after.picks > before.picks) {
+                self.check_reminders(Some(&before), &after);
+            }
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Drawboy");
+            if let Some(error) = &*self.load_error.read().unwrap() {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
 
             if let Some(text) = &wif.text {
                 if let Some(title) = &text.title {
@@ -476,19 +1235,65 @@ impl eframe::App for MyApp {
                     ui.label(format!("Author: {author}"));
                 }
             }
+            self.show_structure(ui, &draft);
+            self.show_colourway_previews(ui, &original);
 
             ui.group(|ui| {
-                if self.mode == OperationMode::Threading {
-                    self.show_threading(ui, wif, shafts, last_row);
+                if motif_mode {
+                    self.show_motifs(ui, &motifs, last_row);
+                } else if self.mode == OperationMode::Threading {
+                    self.show_thread_notes(ui);
+                    self.show_threading(ui, wif, &draft, shafts, last_row, &batches);
+                } else if self.mode == OperationMode::Skeleton {
+                    self.show_skeleton_treadling(ui, &draft, last_row);
+                } else if self.mode == OperationMode::Changes {
+                    self.show_changes(ui, &draft, last_row);
+                } else if self.mode == OperationMode::Drawloom {
+                    self.show_drawloom(ui, &draft, &draw_steps);
+                } else if self.mode == OperationMode::Heddles {
+                    self.show_heddles(ui, &draft);
+                } else if let Some(denting) = &denting {
+                    self.show_sleying(ui, &draft, denting);
+                } else if self.mode == OperationMode::Verify {
+                    self.show_verification(ui, &draft);
+                } else if let Some(sections) = &sections {
+                    self.show_sectional(ui, sections);
                 } else {
-                    self.show_liftplan(ui, wif, shafts, last_row);
+                    self.show_liftplan(ui, &draft, shafts, last_row);
                 }
             });
         });
+
+        if self.weaving_mode() {
+            self.send_to_dobby(&draft);
+        }
+    }
+}
+
+fn count_up(var: &mut u32, last_row: u32) {
+    *var += 1;
+    if *var > last_row {
+        *var = 1;
+    }
+}
+
+fn count_down(var: &mut u32, last_row: u32) {
+    if let Some(new_row) = var.checked_sub(1) {
+        if new_row == 0 {
+            *var = last_row;
+        } else {
+            *var = new_row;
+        }
+    } else {
+        *var = last_row;
     }
 }
 
 fn colour_block(ui: &mut Ui, colour: Color32, highlight: bool) {
+    labelled_colour_block(ui, colour, " ", highlight);
+}
+
+fn labelled_colour_block(ui: &mut Ui, colour: Color32, label: &str, highlight: bool) {
     let stroke_color = if highlight {
         Color32::WHITE
     } else {
@@ -500,11 +1305,29 @@ fn colour_block(ui: &mut Ui, colour: Color32, highlight: bool) {
         .fill(colour)
         .stroke(Stroke::new(1., stroke_color));
 
+    // Dark text on light colours, light text on dark ones.
+    let [r, g, b, _] = colour.to_array();
+    let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+    let text_colour = if luma > 128. {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    };
     frame.show(ui, |ui| {
-        ui.label(" ");
+        ui.label(RichText::new(label).color(text_colour));
     });
 }
 
+fn row_text_size(offset: i32) -> f32 {
+    if offset == 0 {
+        64.
+    } else if offset.abs() == 1 {
+        32.
+    } else {
+        16.
+    }
+}
+
 fn text_block(ui: &mut Ui, text: impl Into<WidgetText>, active_row: bool, active_col: bool) {
     let (bg_color, stroke_color) = match (active_row, active_col) {
         (true, true) => (Color32::DARK_GRAY, Color32::WHITE),
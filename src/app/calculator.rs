@@ -0,0 +1,129 @@
+use eframe::egui::{self, Color32, DragValue, Grid, RichText};
+
+use crate::{
+    calculator::{estimate, ColourNeed},
+    draft::Draft,
+};
+
+use super::{colour_block, MyApp};
+
+impl MyApp {
+    /// Window for planning how much warp to wind and how much yarn to buy.
+    pub(super) fn calculator_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_calculator_window;
+        egui::Window::new("Warp calculator")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let project = &mut self.project;
+                Grid::new("project inputs").show(ui, |ui| {
+                    ui.label("Finished length");
+                    ui.add(
+                        DragValue::new(&mut project.finished_length)
+                            .range(1.0..=10000.0)
+                            .suffix("\""),
+                    );
+                    ui.end_row();
+                    ui.label("Loom waste");
+                    ui.add(
+                        DragValue::new(&mut project.loom_waste)
+                            .range(0.0..=200.0)
+                            .suffix("\""),
+                    );
+                    ui.end_row();
+                    ui.label("Take-up");
+                    percent(ui, &mut project.take_up);
+                    ui.end_row();
+                    ui.label("Shrinkage");
+                    percent(ui, &mut project.shrinkage);
+                    ui.end_row();
+                    ui.label("Warp yarn");
+                    ui.add(
+                        DragValue::new(&mut project.warp_yards_per_pound)
+                            .range(1.0..=50000.0)
+                            .suffix(" yd/lb"),
+                    );
+                    ui.end_row();
+                    ui.label("Weft yarn");
+                    ui.add(
+                        DragValue::new(&mut project.weft_yards_per_pound)
+                            .range(1.0..=50000.0)
+                            .suffix(" yd/lb"),
+                    );
+                    ui.end_row();
+                });
+                ui.horizontal(|ui| {
+                    let mut epi = self.epi(draft);
+                    ui.label("Ends per inch");
+                    if ui
+                        .add(DragValue::new(&mut epi).range(1.0..=200.0).speed(0.5))
+                        .changed()
+                    {
+                        self.epi = Some(epi);
+                    }
+                    let mut ppi = self.ppi(draft);
+                    ui.label("Picks per inch");
+                    if ui
+                        .add(DragValue::new(&mut ppi).range(1.0..=200.0).speed(0.5))
+                        .changed()
+                    {
+                        self.ppi = Some(ppi);
+                    }
+                });
+                ui.separator();
+
+                let needs = estimate(draft, &self.project, self.epi(draft), self.ppi(draft));
+                ui.label(format!(
+                    "{} ends, {:.1}\" wide in the reed",
+                    draft.ends(),
+                    needs.width
+                ));
+                ui.label(format!(
+                    "Wind a warp {:.0}\" ({:.2} yd) long; weave {:.0}\" ({} picks)",
+                    needs.warp_length,
+                    needs.warp_length / 36.,
+                    needs.woven_length,
+                    needs.picks
+                ));
+                Grid::new("yarn needs").striped(true).show(ui, |ui| {
+                    for heading in ["", "Colour", "Threads", "Yards", "Pounds"] {
+                        ui.label(RichText::new(heading).strong());
+                    }
+                    ui.end_row();
+                    need_rows(ui, "Warp", &needs.warp, self.project.warp_yards_per_pound);
+                    need_rows(ui, "Weft", &needs.weft, self.project.weft_yards_per_pound);
+                    let pounds = needs.warp_yards() / self.project.warp_yards_per_pound
+                        + needs.weft_yards() / self.project.weft_yards_per_pound;
+                    ui.label(RichText::new("Total").strong());
+                    ui.label("");
+                    ui.label("");
+                    ui.label(format!("{:.0}", needs.warp_yards() + needs.weft_yards()));
+                    ui.label(RichText::new(format!("{pounds:.2}")).strong());
+                    ui.end_row();
+                });
+            });
+        self.show_calculator_window = open;
+    }
+}
+
+/// Edits a fraction as a percentage.
+fn percent(ui: &mut egui::Ui, fraction: &mut f32) {
+    let mut value = *fraction * 100.;
+    if ui
+        .add(DragValue::new(&mut value).range(0.0..=50.0).suffix("%"))
+        .changed()
+    {
+        *fraction = value / 100.;
+    }
+}
+
+fn need_rows(ui: &mut egui::Ui, label: &str, needs: &[ColourNeed], yards_per_pound: f32) {
+    for (i, need) in needs.iter().enumerate() {
+        ui.label(if i == 0 { label } else { "" });
+        let [r, g, b] = need.colour;
+        colour_block(ui, Color32::from_rgb(r, g, b), false);
+        ui.label(need.threads.to_string());
+        ui.label(format!("{:.0}", need.yards));
+        ui.label(format!("{:.2}", need.yards / yards_per_pound));
+        ui.end_row();
+    }
+}
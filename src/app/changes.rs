@@ -0,0 +1,126 @@
+use eframe::egui::{self, Color32, Layout, RichText, Stroke, Ui, Vec2};
+use egui_extras::{Size, StripBuilder};
+
+use crate::draft::{bit, Draft, Mask};
+
+use super::MyApp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lever {
+    Raise,
+    Lower,
+    StayUp,
+    StayDown,
+}
+
+impl Lever {
+    fn between(before: Mask, after: Mask, shaft: u32) -> Self {
+        match (before & bit(shaft) != 0, after & bit(shaft) != 0) {
+            (false, true) => Lever::Raise,
+            (true, false) => Lever::Lower,
+            (true, true) => Lever::StayUp,
+            (false, false) => Lever::StayDown,
+        }
+    }
+}
+
+impl MyApp {
+    /// Table loom view: which levers to flip to get from the last row to this
+    /// one, with the flips for the row after underneath.
+    pub(super) fn show_changes(&mut self, ui: &mut egui::Ui, draft: &Draft, last_row: u32) {
+        if !draft.has_liftplan() {
+            ui.label("This draft has no liftplan, so there are no levers to change.");
+            return;
+        }
+        let shafts = draft.shafts;
+        // Weaving wraps around from the last row back to the first, but
+        // before the first pick every lever is still down.
+        let previous = if self.picks_woven(draft) == 0 {
+            0
+        } else if self.row <= 1 {
+            draft.lift(last_row)
+        } else {
+            draft.lift(self.row - 1)
+        };
+        let next = if self.row >= last_row {
+            1
+        } else {
+            self.row + 1
+        };
+        let rows = [
+            (
+                format!("Row {}", self.row),
+                previous,
+                draft.lift(self.row),
+                64.,
+            ),
+            (
+                format!("Next: row {next}"),
+                draft.lift(self.row),
+                draft.lift(next),
+                32.,
+            ),
+        ];
+
+        ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        StripBuilder::new(ui)
+            .cell_layout(Layout::centered_and_justified(egui::Direction::LeftToRight))
+            .size(Size::exact(20.))
+            .size(Size::exact(100.))
+            .size(Size::exact(20.))
+            .size(Size::exact(50.))
+            .size(Size::exact(20.))
+            .vertical(|mut strip| {
+                for (label, before, after, text_size) in rows {
+                    let changes = (1..=shafts)
+                        .filter(|&shaft| {
+                            matches!(
+                                Lever::between(before, after, shaft),
+                                Lever::Raise | Lever::Lower
+                            )
+                        })
+                        .count();
+                    strip.cell(|ui| {
+                        ui.label(format!("{label} ({changes} levers to change)"));
+                    });
+                    strip.strip(|sb| {
+                        sb.sizes(Size::relative(1. / shafts as f32), shafts as usize)
+                            .horizontal(|mut strip| {
+                                for shaft in self.loom.orientation.shaft_order(shafts) {
+                                    strip.cell(|ui| {
+                                        lever_block(
+                                            ui,
+                                            shaft,
+                                            Lever::between(before, after, shaft),
+                                            text_size,
+                                        );
+                                    });
+                                }
+                            });
+                    });
+                }
+                strip.cell(|ui| {
+                    ui.label("⬆ raise   ⬇ lower");
+                });
+            });
+    }
+}
+
+fn lever_block(ui: &mut Ui, shaft: u32, lever: Lever, text_size: f32) {
+    let (text, bg_color, text_color) = match lever {
+        Lever::Raise => (format!("⬆{shaft}"), Color32::DARK_GREEN, Color32::WHITE),
+        Lever::Lower => (format!("⬇{shaft}"), Color32::DARK_RED, Color32::WHITE),
+        Lever::StayUp => (shaft.to_string(), Color32::DARK_GRAY, Color32::LIGHT_GRAY),
+        Lever::StayDown => (shaft.to_string(), Color32::BLACK, Color32::DARK_GRAY),
+    };
+    let frame = egui::Frame::none()
+        .inner_margin(0.)
+        .outer_margin(0.)
+        .fill(bg_color)
+        .stroke(Stroke::new(1., text_color));
+
+    frame.show(ui, |ui| {
+        ui.style_mut().visuals.override_text_color = Some(text_color);
+        ui.label(RichText::new(text).size(text_size));
+    });
+}
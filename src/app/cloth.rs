@@ -0,0 +1,51 @@
+use eframe::egui::{self, ColorImage, ScrollArea, Slider, TextureOptions};
+
+use crate::{
+    cloth::{render, Sett, MAX_SIZE},
+    draft::Draft,
+};
+
+use super::MyApp;
+
+impl MyApp {
+    /// Window showing the cloth as it will look woven, drawn again only when
+    /// the draft, sett or zoom changes.
+    pub(super) fn cloth_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_cloth_window;
+        egui::Window::new("Cloth simulation")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.add(
+                    Slider::new(&mut self.cloth_zoom, 20.0..=600.0)
+                        .logarithmic(true)
+                        .text("pixels per inch"),
+                );
+                let sett = Sett::new(draft, self.epi(draft), self.ppi(draft));
+                let stale = !matches!(
+                    &self.cloth,
+                    Some((drawn, drawn_sett, zoom, _))
+                        if drawn == draft && *drawn_sett == sett && *zoom == self.cloth_zoom
+                );
+                if stale {
+                    let cloth = render(draft, &sett, self.cloth_zoom);
+                    let image = ColorImage::from_rgba_unmultiplied(
+                        [cloth.width, cloth.height],
+                        &cloth.pixels,
+                    );
+                    let texture = ctx.load_texture("cloth", image, TextureOptions::LINEAR);
+                    self.cloth = Some((draft.clone(), sett, self.cloth_zoom, texture));
+                }
+                let Some((_, _, _, texture)) = &self.cloth else {
+                    return;
+                };
+                let size = texture.size();
+                if size[0] == MAX_SIZE || size[1] == MAX_SIZE {
+                    ui.label("Showing as much of the cloth as fits at this zoom.");
+                }
+                ScrollArea::both().max_height(600.).show(ui, |ui| {
+                    ui.image((texture.id(), texture.size_vec2()));
+                });
+            });
+        self.show_cloth_window = open;
+    }
+}
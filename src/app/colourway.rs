@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use eframe::egui::{self, Color32, ComboBox, Rect, RichText, ScrollArea, Sense, TextEdit, Vec2};
+
+use crate::{
+    colourway::{repeat, Colourway, Colourways},
+    draft::Draft,
+    loom::Side,
+};
+
+use super::MyApp;
+
+/// Size of a drawdown preview, in points.
+const PREVIEW_SIZE: f32 = 120.;
+/// The most ends and picks a preview shows.
+const PREVIEW_THREADS: u32 = 64;
+
+impl MyApp {
+    /// The colourways tried for the current draft.
+    pub(super) fn colourways(&self) -> &Colourways {
+        static NONE: Colourways = Colourways {
+            colourways: vec![],
+            active: None,
+        };
+        self.colourways.get(&self.draft_key()).unwrap_or(&NONE)
+    }
+
+    /// The draft in its own colours and in each colourway, side by side.
+    /// Clicking one winds and weaves with it. `draft` is in the file's own
+    /// colours.
+    pub(super) fn show_colourway_previews(&mut self, ui: &mut egui::Ui, draft: &Draft) {
+        if self.colourways().colourways.is_empty() {
+            return;
+        }
+        let mut active = self.colourways().active;
+        ScrollArea::horizontal()
+            .id_salt("colourway previews")
+            .show(ui, |ui| {
+                ui.horizontal_top(|ui| {
+                    if self.colourway_preview(ui, "Draft's colours", draft, active.is_none()) {
+                        active = None;
+                    }
+                    for (i, colourway) in self.colourways().colourways.iter().enumerate() {
+                        let chosen = active == Some(i);
+                        if self.colourway_preview(
+                            ui,
+                            &colourway.name,
+                            &colourway.apply(draft),
+                            chosen,
+                        ) {
+                            active = Some(i);
+                        }
+                    }
+                });
+            });
+        if active != self.colourways().active {
+            self.colourways.entry(self.draft_key()).or_default().active = active;
+        }
+    }
+
+    /// Window for trying other colours on the draft without changing the
+    /// file: a choice of which to weave, and an editor for each colourway.
+    /// `draft` is in the file's own colours.
+    pub(super) fn colourways_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_colourways_window;
+        egui::Window::new("Colourways")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let key = self.draft_key();
+                if key.is_empty() {
+                    ui.label("This draft isn't from a file, so its colourways won't be kept.");
+                }
+                let colourways = self.colourways.entry(key).or_default();
+                let name = |active: Option<usize>| {
+                    active
+                        .and_then(|i| colourways.colourways.get(i))
+                        .map(|colourway| colourway.name.clone())
+                        .unwrap_or_else(|| "Draft's colours".to_string())
+                };
+                let mut active = colourways.active;
+                ComboBox::from_label("Wind and weave with")
+                    .selected_text(name(active))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut active, None, name(None));
+                        for i in 0..colourways.colourways.len() {
+                            ui.selectable_value(&mut active, Some(i), name(Some(i)));
+                        }
+                    });
+                colourways.active = active;
+                if ui.button("New colourway").clicked() {
+                    colourways.colourways.push(Colourway::default());
+                }
+
+                let mut removed = None;
+                for (i, colourway) in colourways.colourways.iter_mut().enumerate() {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.add(TextEdit::singleline(&mut colourway.name).desired_width(160.));
+                        if ui.button("Delete").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                    ui.push_id(i, |ui| colourway_editor(ui, colourway, draft));
+                }
+                if let Some(i) = removed {
+                    colourways.remove(i);
+                }
+            });
+        self.show_colourways_window = open;
+    }
+
+    /// A corner of the drawdown in `draft`'s colours, under `name`, outlined
+    /// if it's `chosen`. Returns whether it was clicked.
+    fn colourway_preview(
+        &self,
+        ui: &mut egui::Ui,
+        name: &str,
+        draft: &Draft,
+        chosen: bool,
+    ) -> bool {
+        let ends = draft.ends().min(PREVIEW_THREADS);
+        let picks = draft.picks().min(PREVIEW_THREADS);
+        let cell = (PREVIEW_SIZE / ends.max(picks).max(1) as f32)
+            .floor()
+            .max(1.);
+        let end_one_on_left = self.loom.orientation.end_one == Side::Left;
+        ui.vertical(|ui| {
+            ui.label(name);
+            let size = Vec2::new(ends as f32, picks as f32) * cell;
+            let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+            let painter = ui.painter_at(rect.expand(2.));
+            for row in 0..picks {
+                let pick = row + 1;
+                for column in 0..ends {
+                    let end = if end_one_on_left {
+                        column + 1
+                    } else {
+                        ends - column
+                    };
+                    let [r, g, b] = if draft.lift(pick) & draft.threading(end) != 0 {
+                        draft.warp_colour(end)
+                    } else {
+                        draft.weft_colour(pick)
+                    };
+                    let min = rect.min + Vec2::new(column as f32, row as f32) * cell;
+                    painter.rect_filled(
+                        Rect::from_min_size(min, Vec2::splat(cell)),
+                        0.,
+                        Color32::from_rgb(r, g, b),
+                    );
+                }
+            }
+            if chosen {
+                painter.rect_stroke(rect.expand(1.), 0., ui.visuals().selection.stroke);
+            }
+            response.clicked()
+        })
+        .inner
+    }
+}
+
+/// The colour table with what each entry is woven as, then the warp and
+/// weft sequences.
+fn colourway_editor(ui: &mut egui::Ui, colourway: &mut Colourway, draft: &Draft) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Colours");
+        for (entry, mut colour) in colourway.colour_table(draft) {
+            ui.label(entry.to_string());
+            if let Some(&drafted) = draft.colour_table().get(&entry) {
+                swatch(ui, drafted);
+                ui.label("→");
+            }
+            if ui.color_edit_button_srgb(&mut colour).changed() {
+                colourway.replace(draft, entry, colour);
+            }
+        }
+        if ui.small_button("+").clicked() {
+            colourway.add_colour(draft, [255; 3]);
+        }
+    });
+    let table = colourway.colour_table(draft);
+    let warp: Vec<_> = (1..=draft.ends())
+        .map(|end| draft.warp_entry(end))
+        .collect();
+    let weft: Vec<_> = (1..=draft.picks())
+        .map(|pick| draft.weft_entry(pick))
+        .collect();
+    sequence_editor(ui, "Warp", &mut colourway.warp, &warp, &table);
+    sequence_editor(ui, "Weft", &mut colourway.weft, &weft, &table);
+}
+
+/// A sequence of colour table entries to repeat, or the draft's own if it's
+/// empty.
+fn sequence_editor(
+    ui: &mut egui::Ui,
+    label: &str,
+    sequence: &mut Vec<u32>,
+    draft: &[u32],
+    table: &BTreeMap<u32, [u8; 3]>,
+) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(label);
+        if sequence.is_empty() {
+            ui.label("as drafted");
+            if ui.button("Change").clicked() {
+                *sequence = repeat(draft);
+            }
+            return;
+        }
+        for (i, entry) in sequence.iter_mut().enumerate() {
+            ComboBox::from_id_salt((label, i))
+                .width(24.)
+                .selected_text(entry_text(table, *entry))
+                .show_ui(ui, |ui| {
+                    for &choice in table.keys() {
+                        ui.selectable_value(entry, choice, entry_text(table, choice));
+                    }
+                });
+        }
+        if ui.small_button("+").clicked() {
+            let last = sequence.last().copied().unwrap_or(1);
+            sequence.push(last);
+        }
+        if ui.small_button("−").clicked() {
+            sequence.pop();
+        }
+        if ui.button("As drafted").clicked() {
+            sequence.clear();
+        }
+    });
+}
+
+/// A colour table entry's number on its colour.
+fn entry_text(table: &BTreeMap<u32, [u8; 3]>, entry: u32) -> RichText {
+    let [r, g, b] = table.get(&entry).copied().unwrap_or_default();
+    let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+    let text_colour = if luma > 128. {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    };
+    RichText::new(format!(" {entry} "))
+        .color(text_colour)
+        .background_color(Color32::from_rgb(r, g, b))
+}
+
+/// The draft's own colour, which can't be edited here.
+fn swatch(ui: &mut egui::Ui, [r, g, b]: [u8; 3]) {
+    let size = ui.spacing().interact_size;
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    ui.painter()
+        .rect_filled(rect, 2., Color32::from_rgb(r, g, b));
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use eframe::egui::{self, ComboBox, DragValue};
+
+use crate::{
+    dobby::{DobbyLink, DobbyProtocol},
+    draft::Draft,
+};
+
+use super::MyApp;
+
+impl MyApp {
+    pub(super) fn dobby_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_dobby_window;
+        self.check_dobby();
+        egui::Window::new("Dobby output")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let connected = self.dobby_link.is_some();
+                ui.add_enabled_ui(!connected, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Port");
+                        ui.text_edit_singleline(&mut self.dobby.port);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Baud");
+                        ui.add(DragValue::new(&mut self.dobby.baud).range(300..=115_200u32));
+                    });
+                    ComboBox::from_label("Protocol")
+                        .selected_text(self.dobby.protocol.name())
+                        .show_ui(ui, |ui| {
+                            for protocol in DobbyProtocol::ALL {
+                                ui.selectable_value(
+                                    &mut self.dobby.protocol,
+                                    protocol,
+                                    protocol.name(),
+                                );
+                            }
+                        });
+                });
+
+                if connected {
+                    ui.label(format!("Sending picks to {}", self.dobby.port));
+                    if ui.button("Disconnect").clicked() {
+                        self.dobby_link = None;
+                    }
+                } else if ui.button("Connect").clicked() {
+                    match DobbyLink::open(&self.dobby, ctx.clone(), Arc::clone(&self.shed_opened)) {
+                        Ok(link) => {
+                            self.dobby_link = Some(link);
+                            self.dobby_error = None;
+                        }
+                        Err(e) => self.dobby_error = Some(e),
+                    }
+                }
+                if let Some(error) = &self.dobby_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+        self.show_dobby_window = open;
+    }
+
+    /// Disconnects from the loom if it stopped answering.
+    fn check_dobby(&mut self) {
+        if let Some(e) = self.dobby_link.as_ref().and_then(DobbyLink::error) {
+            self.dobby_error = Some(format!("Lost connection to the dobby: {e}"));
+            self.dobby_link = None;
+        }
+    }
+
+    /// Keeps the loom's shed in step with the current row.
+    pub(super) fn send_to_dobby(&mut self, draft: &Draft) {
+        self.check_dobby();
+        let Some(link) = &mut self.dobby_link else {
+            return;
+        };
+        if let Err(e) = link.send(self.row, draft.lift(self.row), draft.shafts) {
+            self.dobby_error = Some(format!("Lost connection to the dobby: {e}"));
+            self.dobby_link = None;
+        }
+    }
+}
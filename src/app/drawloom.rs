@@ -0,0 +1,136 @@
+use eframe::egui::{self, Layout, RichText, Vec2};
+use egui_extras::{Size, StripBuilder};
+
+use crate::{
+    draft::{bit, members, Draft, Mask},
+    drawloom::DrawStep,
+};
+
+use super::{text_block, MyApp};
+
+impl MyApp {
+    /// Drawloom view: pattern pulls alternate with the ground picks woven under them.
+    pub(super) fn show_drawloom(&mut self, ui: &mut egui::Ui, draft: &Draft, steps: &[DrawStep]) {
+        let Some(step) = (self.draw_step as usize)
+            .checked_sub(1)
+            .and_then(|i| steps.get(i))
+        else {
+            ui.label("This draft has no picks to weave.");
+            return;
+        };
+        let ground_shafts = self.loom.ground_shafts.min(draft.shafts);
+        let (ground, pattern): (Vec<_>, Vec<_>) = self
+            .loom
+            .orientation
+            .shaft_order(draft.shafts)
+            .into_iter()
+            .partition(|&shaft| shaft <= ground_shafts);
+        let units = self.loom.draw_units.name();
+        let pattern_rows = steps.last().map(DrawStep::pattern_row).unwrap_or(0);
+
+        ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        StripBuilder::new(ui)
+            .cell_layout(Layout::centered_and_justified(egui::Direction::LeftToRight))
+            .size(Size::exact(20.))
+            .size(Size::exact(80.))
+            .size(Size::exact(40.))
+            .size(Size::remainder())
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    ui.label(format!(
+                        "Pattern row {} of {pattern_rows}, pick {}",
+                        step.pattern_row(),
+                        step.pick()
+                    ));
+                });
+                match *step {
+                    DrawStep::Pull {
+                        pull,
+                        release,
+                        drawn,
+                        ..
+                    } => {
+                        strip.cell(|ui| {
+                            ui.label(
+                                RichText::new(format!(
+                                    "Pull {units}s {}\nRelease {units}s {}",
+                                    list(pull),
+                                    list(release)
+                                ))
+                                .size(28.),
+                            );
+                        });
+                        strip.strip(|sb| shaft_row(sb, &pattern, drawn, 18.));
+                    }
+                    DrawStep::Ground {
+                        treadles, lifted, ..
+                    } => {
+                        strip.strip(|sb| shaft_row(sb, &ground, lifted, 64.));
+                        strip.cell(|ui| {
+                            ui.label(ground_text(treadles, lifted));
+                        });
+                    }
+                }
+                strip.cell(|ui| {
+                    let upcoming: Vec<_> = steps
+                        .iter()
+                        .skip(self.draw_step as usize)
+                        .take(4)
+                        .map(|step| match *step {
+                            DrawStep::Pull { pull, release, .. } => {
+                                format!("Pull {units}s {}, release {}", list(pull), list(release))
+                            }
+                            DrawStep::Ground {
+                                pick,
+                                treadles,
+                                lifted,
+                                ..
+                            } => {
+                                format!("Pick {pick}: {}", ground_text(treadles, lifted))
+                            }
+                        })
+                        .collect();
+                    ui.label(format!("Then:\n{}", upcoming.join("\n")));
+                });
+            });
+    }
+}
+
+fn shaft_row(sb: StripBuilder, shafts: &[u32], raised: Mask, text_size: f32) {
+    let count = shafts.len().max(1);
+    sb.sizes(Size::relative(1. / count as f32), count)
+        .horizontal(|mut strip| {
+            for &shaft in shafts {
+                strip.cell(|ui| {
+                    text_block(
+                        ui,
+                        RichText::new(shaft.to_string()).size(text_size),
+                        true,
+                        raised & bit(shaft) != 0,
+                    );
+                });
+            }
+        });
+}
+
+/// The treadles to press for a ground pick, if it's treadled, and the
+/// shafts they lift.
+fn ground_text(treadles: Option<Mask>, lifted: Mask) -> String {
+    match treadles {
+        Some(treadles) => format!(
+            "Treadles {}, ground shafts {}",
+            list(treadles),
+            list(lifted)
+        ),
+        None => format!("Ground shafts {}", list(lifted)),
+    }
+}
+
+fn list(mask: Mask) -> String {
+    let members: Vec<_> = members(mask).map(|n| n.to_string()).collect();
+    if members.is_empty() {
+        "none".to_string()
+    } else {
+        members.join(", ")
+    }
+}
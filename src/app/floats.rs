@@ -0,0 +1,126 @@
+use eframe::egui::{self, Color32, DragValue, Rect, ScrollArea, Vec2};
+
+use crate::{
+    draft::Draft,
+    floats::{Floats, Longest},
+    loom::Side,
+};
+
+use super::MyApp;
+
+impl MyApp {
+    /// Window for vetting a draft's floats: the longest ones, and a
+    /// drawdown with the long floats picked out.
+    pub(super) fn floats_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_floats_window;
+        egui::Window::new("Float check")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if !draft.has_liftplan() {
+                    ui.label("This draft has no liftplan, so its floats can't be checked.");
+                    return;
+                }
+                let floats = Floats::new(draft);
+                let limit = self.float_limit;
+                ui.horizontal(|ui| {
+                    ui.label("Longest acceptable float");
+                    ui.add(DragValue::new(&mut self.float_limit).range(1..=64));
+                    ui.label("threads");
+                });
+                self.float_summary(ui, "warp", floats.longest_warp(), draft.weft_spacing);
+                self.float_summary(ui, "weft", floats.longest_weft(), draft.warp_spacing);
+                let over = floats.cells_over(limit);
+                if over > 0 {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!("{over} cells of the drawdown are in floats over {limit}"),
+                    );
+                }
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.float_heat_map, "Heat map");
+                    ui.label("Zoom");
+                    ui.add(DragValue::new(&mut self.float_zoom).range(2.0..=24.0));
+                });
+                ui.separator();
+                self.float_drawdown(ui, &floats);
+            });
+        self.show_floats_window = open;
+    }
+
+    fn float_summary(
+        &self,
+        ui: &mut egui::Ui,
+        kind: &str,
+        longest: Option<Longest>,
+        spacing: Option<f32>,
+    ) {
+        let Some(longest) = longest else {
+            return;
+        };
+        let inches = spacing
+            .map(|spacing| format!(" ({:.2}\")", longest.length as f32 * spacing))
+            .unwrap_or_default();
+        let text = format!(
+            "Longest {kind} float: {}{inches}, at end {}, pick {}",
+            longest.length, longest.end, longest.pick
+        );
+        if longest.length > self.float_limit {
+            ui.colored_label(ui.visuals().warn_fg_color, text);
+        } else {
+            ui.label(text);
+        }
+    }
+
+    /// The drawdown, warp up in dark grey and weft up in light grey, with
+    /// floats over the limit in red, or shaded by length for a heat map.
+    fn float_drawdown(&self, ui: &mut egui::Ui, floats: &Floats) {
+        let cell = self.float_zoom;
+        let limit = self.float_limit;
+        let size = Vec2::new(floats.ends as f32, floats.picks as f32) * cell;
+        let end_one_on_left = self.loom.orientation.end_one == Side::Left;
+        ScrollArea::both()
+            .max_height(500.)
+            .show_viewport(ui, |ui, viewport| {
+                let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                // Only draw the cells in view.
+                let columns = (viewport.left() / cell).floor().max(0.) as u32
+                    ..((viewport.right() / cell).ceil() as u32).min(floats.ends);
+                let rows = (viewport.top() / cell).floor().max(0.) as u32
+                    ..((viewport.bottom() / cell).ceil() as u32).min(floats.picks);
+                for row in rows {
+                    let pick = row + 1;
+                    for column in columns.clone() {
+                        let end = if end_one_on_left {
+                            column + 1
+                        } else {
+                            floats.ends - column
+                        };
+                        let (warp, weft) = (floats.warp(end, pick), floats.weft(end, pick));
+                        let length = warp.max(weft);
+                        let fill = if self.float_heat_map {
+                            heat(length, limit)
+                        } else if length > limit {
+                            Color32::RED
+                        } else if warp > 0 {
+                            Color32::DARK_GRAY
+                        } else {
+                            Color32::LIGHT_GRAY
+                        };
+                        let min = rect.min + Vec2::new(column as f32, row as f32) * cell;
+                        painter.rect_filled(Rect::from_min_size(min, Vec2::splat(cell)), 0., fill);
+                    }
+                }
+            });
+    }
+}
+
+/// Blue for the shortest floats through to red at the limit and beyond.
+fn heat(length: u32, limit: u32) -> Color32 {
+    let t = (length.saturating_sub(1) as f32 / limit.saturating_sub(1).max(1) as f32).min(1.);
+    Color32::from_rgb(
+        (255. * t) as u8,
+        (64. * (1. - t)) as u8,
+        (255. * (1. - t)) as u8,
+    )
+}
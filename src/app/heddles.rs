@@ -0,0 +1,82 @@
+use eframe::egui::{self, Grid, RichText};
+
+use crate::{draft::Draft, heddles::plan, loom::Side};
+
+use super::MyApp;
+
+impl MyApp {
+    /// Heddle preparation: how many heddles each shaft needs either side of
+    /// the centre, stepping through the shafts one side at a time.
+    pub(super) fn show_heddles(&mut self, ui: &mut egui::Ui, draft: &Draft) {
+        let orientation = self.loom.orientation;
+        let plan = plan(
+            draft,
+            self.loom.heddles_per_shaft,
+            &orientation.shaft_order(draft.shafts),
+            orientation.end_one == Side::Left,
+        );
+        let step = self.heddle_step.max(1);
+        let current = plan.get(((step - 1) / 2) as usize);
+
+        if let Some(shaft) = current {
+            // Each shaft starts on the side we thread from.
+            let first_side = step % 2 == 1;
+            let left_side = first_side == (orientation.start == Side::Left);
+            let (side, count) = if left_side {
+                ("left", shaft.left)
+            } else {
+                ("right", shaft.right)
+            };
+            ui.label(
+                RichText::new(format!(
+                    "Shaft {}: slide {count} heddles to the {side} of centre",
+                    shaft.shaft
+                ))
+                .size(32.),
+            );
+            if !first_side && shaft.surplus > 0 {
+                ui.label(
+                    RichText::new(format!(
+                        "Then push the other {} out to the ends of the shaft",
+                        shaft.surplus
+                    ))
+                    .size(24.),
+                );
+            }
+            if shaft.shortfall > 0 {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!("This shaft is {} heddles short", shaft.shortfall),
+                );
+            }
+        }
+
+        ui.separator();
+        Grid::new("heddle plan").striped(true).show(ui, |ui| {
+            for heading in ["Shaft", "Needed", "Left", "Right", "Surplus"] {
+                ui.label(RichText::new(heading).strong());
+            }
+            ui.end_row();
+            for shaft in &plan {
+                let highlight = current.is_some_and(|current| current.shaft == shaft.shaft);
+                let cell = |text: String| {
+                    if highlight {
+                        RichText::new(text).strong().underline()
+                    } else {
+                        RichText::new(text)
+                    }
+                };
+                ui.label(cell(shaft.shaft.to_string()));
+                ui.label(cell(shaft.needed().to_string()));
+                ui.label(cell(shaft.left.to_string()));
+                ui.label(cell(shaft.right.to_string()));
+                ui.label(cell(if shaft.shortfall > 0 {
+                    format!("{} short", shaft.shortfall)
+                } else {
+                    shaft.surplus.to_string()
+                }));
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -0,0 +1,65 @@
+use eframe::egui::{self, DragValue};
+
+use crate::{
+    calculator::estimate,
+    draft::Draft,
+    length::{check, refined_ppi, woven_length, Measurement, PPI_RANGE},
+};
+
+use super::MyApp;
+
+impl MyApp {
+    /// How much cloth is woven and how much is left, with a place to enter
+    /// the picks per inch or a ruler measurement.
+    pub(super) fn woven_length(&mut self, ui: &mut egui::Ui, draft: &Draft) {
+        let picks = self.picks_woven(draft);
+        let ppi = self.ppi(draft);
+        let woven = woven_length(&self.measurements, picks, ppi);
+        let planned = estimate(draft, &self.project, self.epi(draft), ppi).woven_length;
+        ui.label(format!("Woven: {woven:.1}\" of {planned:.0}\""));
+        ui.label(format!("Remaining: {:.1}\"", (planned - woven).max(0.)));
+
+        ui.horizontal(|ui| {
+            let mut ppi = ppi;
+            ui.label("PPI");
+            if ui
+                .add(DragValue::new(&mut ppi).range(PPI_RANGE).speed(0.1))
+                .changed()
+            {
+                self.ppi = Some(ppi);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                DragValue::new(&mut self.measured_length)
+                    .range(0.0..=10000.0)
+                    .speed(0.1)
+                    .suffix("\""),
+            );
+            if ui.button("Measured").clicked() {
+                let measurement = Measurement {
+                    picks,
+                    length: self.measured_length,
+                };
+                // A new measurement replaces any earlier one at the same pick.
+                let mut measurements = self.measurements.clone();
+                measurements.retain(|m| m.picks != picks);
+                self.measurement_error = check(&measurements, &measurement).err();
+                if self.measurement_error.is_none() {
+                    measurements.push(measurement);
+                    self.measurements = measurements;
+                    if let Some(ppi) = refined_ppi(&self.measurements) {
+                        self.ppi = Some(ppi);
+                    }
+                }
+            }
+        });
+        if let Some(error) = &self.measurement_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        if !self.measurements.is_empty() && ui.button("Forget measurements").clicked() {
+            self.measurements.clear();
+            self.measurement_error = None;
+        }
+    }
+}
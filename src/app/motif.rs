@@ -0,0 +1,135 @@
+use std::time::Instant;
+
+use eframe::egui::{self, Layout, RichText, Vec2};
+use egui_extras::{Size, StripBuilder};
+
+use crate::{
+    draft::{Draft, Mask},
+    motif::{self, shafts_label, Motif},
+};
+
+use super::{text_block, MyApp};
+
+impl MyApp {
+    /// The threading in the order it's threaded, following the loom orientation.
+    pub(super) fn threading_steps(&self, draft: &Draft) -> Vec<Mask> {
+        let ends = draft.ends();
+        (1..=ends as i32)
+            .map(|step| {
+                let end = self.loom.orientation.end_at_step(step, ends);
+                draft.threading(end as u32)
+            })
+            .collect()
+    }
+
+    /// Warp colours in the order the ends are threaded.
+    pub(super) fn warp_colour_steps(&self, draft: &Draft) -> Vec<[u8; 3]> {
+        let ends = draft.ends();
+        (1..=ends as i32)
+            .map(|step| {
+                let end = self.loom.orientation.end_at_step(step, ends);
+                draft.warp_colour(end as u32)
+            })
+            .collect()
+    }
+
+    /// Buttons for skipping a whole repeat of the current motif, where
+    /// the pedal and the usual buttons go an end at a time.
+    pub(super) fn motif_buttons(&mut self, ui: &mut egui::Ui, motifs: &[Motif], last_row: u32) {
+        let next = ui.button("Next repeat").clicked();
+        let prev = ui.button("Prev repeat").clicked();
+        if next || prev {
+            self.step_motif(next, motifs, last_row);
+            // Skipping ends isn't threading them, so leave them out of the timings.
+            self.last_t = Instant::now();
+        }
+    }
+
+    /// Moves a whole repeat of the current motif forwards or back.
+    fn step_motif(&mut self, forwards: bool, motifs: &[Motif], last_row: u32) {
+        let orientation = self.loom.orientation;
+        let step = orientation.step_at_end(self.warp, last_row).max(1) as u32;
+        let step = if forwards {
+            motif::next_repeat(motifs, step)
+                .filter(|&step| step <= last_row)
+                .unwrap_or(1)
+        } else {
+            motif::previous_repeat(motifs, step).unwrap_or_else(|| {
+                motifs
+                    .last()
+                    .map(|motif| motif.first_step + motif.len() - motif.ends.len() as u32)
+                    .unwrap_or(1)
+            })
+        };
+        self.warp = orientation.end_at_step(step as i32, last_row) as u32;
+    }
+
+    /// Compressed threading view: the threading as motifs and repeats, with
+    /// the current repeat spelled out.
+    pub(super) fn show_motifs(&mut self, ui: &mut egui::Ui, motifs: &[Motif], last_row: u32) {
+        let step = self
+            .loom
+            .orientation
+            .step_at_end(self.warp, last_row)
+            .max(1) as u32;
+        let Some(index) = motif::find(motifs, step) else {
+            return;
+        };
+        let current = &motifs[index];
+        let (repeat, within) = current.position(step);
+
+        ui.label(
+            RichText::new(format!(
+                "Block {} of {}: {current}",
+                index + 1,
+                motifs.len()
+            ))
+            .size(24.),
+        );
+        ui.label(
+            RichText::new(format!(
+                "Repeat {} of {}, end {} of {}",
+                repeat + 1,
+                current.repeats,
+                within + 1,
+                current.ends.len()
+            ))
+            .size(18.),
+        );
+
+        ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        let cols = current.ends.len();
+        ui.allocate_ui(Vec2::new(ui.available_width(), 80.), |ui| {
+            StripBuilder::new(ui)
+                .cell_layout(Layout::centered_and_justified(egui::Direction::LeftToRight))
+                .sizes(Size::relative(1. / cols as f32), cols)
+                .horizontal(|mut strip| {
+                    for (i, &end) in current.ends.iter().enumerate() {
+                        strip.cell(|ui| {
+                            text_block(
+                                ui,
+                                RichText::new(shafts_label(end)).size(32.),
+                                i as u32 == within,
+                                true,
+                            );
+                        });
+                    }
+                });
+        });
+
+        ui.separator();
+        ui.horizontal_wrapped(|ui| {
+            for (i, motif) in motifs.iter().enumerate() {
+                let text = RichText::new(motif.to_string());
+                ui.label(if i == index {
+                    text.strong().underline()
+                } else {
+                    text
+                });
+                if i + 1 < motifs.len() {
+                    ui.label("then");
+                }
+            }
+        });
+    }
+}
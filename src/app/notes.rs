@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use eframe::egui::{self, RichText, ScrollArea, TextEdit};
+
+use crate::{
+    drawloom::DrawStep,
+    notes::{with_notes, Note, Target},
+};
+
+use super::{MyApp, OperationMode};
+
+impl MyApp {
+    pub(super) fn notes(&self) -> &[Note] {
+        self.notes
+            .get(&self.draft_key())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub(super) fn notes_on(&self, target: Target) -> impl Iterator<Item = &Note> {
+        self.notes()
+            .iter()
+            .filter(move |note| note.target == target)
+    }
+
+    /// Notes on the threads near the current one.
+    pub(super) fn show_thread_notes(&self, ui: &mut egui::Ui) {
+        let nearby = self.warp.saturating_sub(2)..=self.warp + self.threading_batch_size;
+        for note in self.notes() {
+            if matches!(note.target, Target::Thread(end) if nearby.contains(&end)) {
+                ui.label(RichText::new(note.to_string()).size(20.));
+            }
+        }
+    }
+
+    /// Window for adding notes and jumping to them.
+    pub(super) fn notes_window(&mut self, ctx: &egui::Context, draw_steps: &[DrawStep]) {
+        let mut open = self.show_notes_window;
+        egui::Window::new("Notes").open(&mut open).show(ctx, |ui| {
+            if self.draft_key().is_empty() {
+                ui.label("This draft isn't from a file, so its notes won't be kept.");
+            }
+            ui.add(
+                TextEdit::singleline(&mut self.note_text).hint_text("Leave empty for a bookmark"),
+            );
+            let mut added = None;
+            ui.horizontal(|ui| {
+                if ui.button(format!("Add to row {}", self.row)).clicked() {
+                    added = Some(Target::Row(self.row));
+                }
+                if ui.button(format!("Add to thread {}", self.warp)).clicked() {
+                    added = Some(Target::Thread(self.warp));
+                }
+            });
+            if added.is_some() && self.note_text.contains(';') {
+                *self.notes_status.write().unwrap() = Some(Err(
+                    "Notes can't contain ';' if they're to go in a WIF".to_string(),
+                ));
+            } else if let Some(target) = added {
+                let key = self.draft_key();
+                let notes = self.notes.entry(key).or_default();
+                notes.push(Note {
+                    target,
+                    text: std::mem::take(&mut self.note_text),
+                });
+                notes.sort_by_key(|note| note.target);
+            }
+
+            ui.separator();
+            let mut jump = None;
+            let mut remove = None;
+            ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                for (i, note) in self.notes().iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Go").clicked() {
+                            jump = Some(note.target);
+                        }
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.label(note.to_string());
+                    });
+                }
+            });
+            match jump {
+                Some(Target::Row(row)) => {
+                    self.row = row;
+                    if !self.weaving_mode() {
+                        self.mode = OperationMode::Liftplan;
+                    }
+                    // The drawloom view follows its own steps, and sets the row from them.
+                    if let Some(step) = draw_steps.iter().position(|step| step.pick() == row) {
+                        self.draw_step = step as u32 + 1;
+                    }
+                }
+                Some(Target::Thread(end)) => {
+                    self.warp = end;
+                    self.mode = OperationMode::Threading;
+                }
+                None => {}
+            }
+            if let Some(i) = remove {
+                let key = self.draft_key();
+                if let Some(notes) = self.notes.get_mut(&key) {
+                    notes.remove(i);
+                }
+            }
+
+            ui.separator();
+            if ui
+                .button("Save a copy of the WIF with these notes")
+                .clicked()
+            {
+                self.write_notes(ctx);
+            }
+            match &*self.notes_status.read().unwrap() {
+                Some(Ok(message)) => {
+                    ui.label(message);
+                }
+                Some(Err(error)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                None => {}
+            }
+        });
+        self.show_notes_window = open;
+    }
+
+    /// Saves the open WIF, with our notes added to the ones already in it, to
+    /// a file the weaver picks. The file itself is left alone.
+    fn write_notes(&self, ctx: &egui::Context) {
+        let status = Arc::clone(&self.notes_status);
+        let written = self
+            .wif_path
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "The draft wasn't opened from a file".to_string())
+            .and_then(|path| {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Error opening file {}: {e}", path.display()))?;
+                Ok((path, with_notes(&contents, self.notes())?))
+            });
+        let (path, written) = match written {
+            Ok(written) => written,
+            Err(e) => {
+                *status.write().unwrap() = Some(Err(e));
+                return;
+            }
+        };
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let name = path
+                .file_stem()
+                .map(|stem| format!("{} with notes.wif", stem.to_string_lossy()))
+                .unwrap_or_else(|| "notes.wif".to_string());
+            let mut dialog = rfd::FileDialog::new()
+                .add_filter("WIF", &["wif"])
+                .set_title("Save WIF with notes")
+                .set_file_name(name);
+            if let Some(dir) = path.parent() {
+                dialog = dialog.set_directory(dir);
+            }
+            let Some(fname) = dialog.save_file() else {
+                return;
+            };
+            *status.write().unwrap() = Some(match std::fs::write(&fname, written) {
+                Ok(()) => Ok(format!("Saved {}", fname.display())),
+                Err(e) => Err(format!("Error writing file {}: {e}", fname.display())),
+            });
+            ctx.request_repaint();
+        });
+    }
+}
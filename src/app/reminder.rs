@@ -0,0 +1,111 @@
+use eframe::egui::{self, Align2, ComboBox, DragValue, Grid, RichText, TextEdit};
+
+use crate::{
+    calculator::estimate,
+    draft::Draft,
+    length::woven_length,
+    reminder::{Progress, Reminder, Trigger},
+};
+
+use super::MyApp;
+
+impl MyApp {
+    pub(super) fn progress(&self, draft: &Draft) -> Progress {
+        let picks = self.picks_woven(draft);
+        let ppi = self.ppi(draft);
+        Progress {
+            row: self.row,
+            repeat: self.repeat,
+            picks,
+            woven: woven_length(&self.measurements, picks, ppi),
+            planned: estimate(draft, &self.project, self.epi(draft), ppi).woven_length,
+            elapsed: self.weaving_time,
+        }
+    }
+
+    /// Queues up the reminders due on moving from `before` to `after`, or on
+    /// starting at `after`.
+    pub(super) fn check_reminders(&mut self, before: Option<&Progress>, after: &Progress) {
+        self.alerts
+            .extend(crate::reminder::due(&self.reminders, before, after));
+    }
+
+    /// The oldest reminder not yet acknowledged, over everything else.
+    pub(super) fn show_alerts(&mut self, ctx: &egui::Context) {
+        let Some(alert) = self.alerts.first() else {
+            return;
+        };
+        let mut acknowledged = false;
+        egui::Window::new("Reminder")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.label(RichText::new(alert).size(48.).strong());
+                if self.alerts.len() > 1 {
+                    ui.label(format!("{} more after this", self.alerts.len() - 1));
+                }
+                acknowledged = ui.button(RichText::new("OK (pedal)").size(24.)).clicked();
+            });
+        if acknowledged {
+            self.acknowledge_alert();
+        }
+    }
+
+    pub(super) fn acknowledge_alert(&mut self) {
+        if !self.alerts.is_empty() {
+            self.alerts.remove(0);
+        }
+    }
+
+    /// Window for setting up reminders.
+    pub(super) fn reminders_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_reminders_window;
+        egui::Window::new("Reminders")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let mut remove = None;
+                Grid::new("reminders").striped(true).show(ui, |ui| {
+                    for (i, reminder) in self.reminders.iter_mut().enumerate() {
+                        ui.add(TextEdit::singleline(&mut reminder.message).desired_width(200.));
+                        ComboBox::from_id_salt(("reminder trigger", i))
+                            .selected_text(reminder.trigger.name())
+                            .show_ui(ui, |ui| {
+                                for kind in Trigger::KINDS {
+                                    let same = std::mem::discriminant(&kind)
+                                        == std::mem::discriminant(&reminder.trigger);
+                                    if ui.selectable_label(same, kind.name()).clicked() && !same {
+                                        reminder.trigger = kind;
+                                    }
+                                }
+                            });
+                        match &mut reminder.trigger {
+                            Trigger::Row(n) | Trigger::EveryRows(n) | Trigger::Repeat(n) => {
+                                ui.add(DragValue::new(n).range(1..=100000));
+                            }
+                            Trigger::EveryInches(n) => {
+                                ui.add(DragValue::new(n).range(0.5..=1000.0).suffix("\""));
+                            }
+                            Trigger::EveryMinutes(n) => {
+                                ui.add(DragValue::new(n).range(1.0..=600.0).suffix(" min"));
+                            }
+                            Trigger::Finished => {
+                                ui.label("");
+                            }
+                        }
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = remove {
+                    self.reminders.remove(i);
+                }
+                if ui.button("Add reminder").clicked() {
+                    self.reminders.push(Reminder::default());
+                }
+            });
+        self.show_reminders_window = open;
+    }
+}
@@ -0,0 +1,89 @@
+use eframe::egui::{self, Button, Color32, DragValue, RichText};
+
+use crate::{
+    calculator::estimate,
+    draft::Draft,
+    sectional::{runs, SectionalPlan},
+};
+
+use super::{colour_block, MyApp};
+
+impl MyApp {
+    pub(super) fn sectional_plan(&self, draft: &Draft) -> SectionalPlan {
+        let epi = self.epi(draft);
+        let needs = estimate(draft, &self.project, epi, self.ppi(draft));
+        crate::sectional::plan(
+            draft,
+            epi,
+            self.loom.section_width,
+            needs.warp_length,
+            self.loom.beam_circumference,
+        )
+    }
+
+    /// Sectional warping: loading the spool rack and winding one section of
+    /// the beam at a time.
+    pub(super) fn show_sectional(&mut self, ui: &mut egui::Ui, plan: &SectionalPlan) {
+        let index = self.section_step.max(1) as usize - 1;
+        let Some(section) = plan.sections.get(index) else {
+            ui.label("The draft has no warp.");
+            return;
+        };
+        // A new section starts its turns from zero.
+        if self.turns_section != self.section_step {
+            self.turns_section = self.section_step;
+            self.turns = 0;
+        }
+
+        ui.label(format!(
+            "{} sections of {} ends, {:.2}\" wide, {:.1} turns each",
+            plan.sections.len(),
+            plan.ends_per_section,
+            self.loom.section_width,
+            plan.revolutions
+        ));
+        ui.separator();
+        ui.label(
+            RichText::new(format!(
+                "Section {}: ends {}–{}",
+                self.section_step,
+                section.ends.start,
+                section.ends.end - 1
+            ))
+            .size(32.),
+        );
+
+        let previous = index.checked_sub(1).map(|i| &plan.sections[i]);
+        match previous.map(|previous| section.changes_from(previous)) {
+            None => ui.label(format!("Load {} spools", section.spools.len())),
+            Some(0) => ui.label("The rack stays as it is"),
+            Some(changes) => ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!("Reload the rack: {changes} spools change"),
+            ),
+        };
+        ui.label("Spools, in order through the tension box:");
+        ui.horizontal_wrapped(|ui| {
+            for (colour, count) in runs(&section.spools) {
+                let [r, g, b] = colour;
+                colour_block(ui, Color32::from_rgb(r, g, b), false);
+                ui.label(format!("×{count}"));
+            }
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let count_turn =
+                Button::new(RichText::new("Turn").size(24.)).min_size([96., 48.].into());
+            if ui.add(count_turn).clicked() {
+                self.turns += 1;
+            }
+            ui.add(DragValue::new(&mut self.turns));
+            let target = plan.revolutions.ceil() as u32;
+            ui.label(RichText::new(format!("of {target} turns")).size(24.));
+            if self.turns >= target {
+                ui.label(RichText::new("✔ Section wound").size(24.));
+            }
+        });
+    }
+}
@@ -0,0 +1,78 @@
+use eframe::egui::{self, RichText};
+
+use crate::{
+    draft::Draft,
+    loom::Side,
+    selvedge::{floating_hint, misses, Miss},
+    shuttle::{side_name, throws},
+};
+
+use super::MyApp;
+
+/// How many missed picks to list before summarising the rest.
+const LISTED: usize = 20;
+
+impl MyApp {
+    /// The draft's picks that miss an edge end, thrown by the shuttles as set up.
+    fn selvedge_misses(&self, draft: &Draft) -> Vec<Miss> {
+        let shuttles = self.pick_shuttles(draft);
+        let throws = throws(shuttles.into_iter(), self.shuttles().first_side);
+        let (first, last) = (1, draft.ends());
+        let (left, right) = if self.loom.orientation.end_one == Side::Left {
+            (first, last)
+        } else {
+            (last, first)
+        };
+        misses(draft, &throws, left, right)
+    }
+
+    /// Window reporting which picks miss the selvedges.
+    pub(super) fn selvedge_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_selvedge_window;
+        egui::Window::new("Selvedges")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let misses = self.selvedge_misses(draft);
+                if !draft.has_liftplan() {
+                    ui.label("This draft has no liftplan, so its selvedges can't be checked.");
+                } else if misses.is_empty() {
+                    ui.label("The weft catches the edge ends on every pick.");
+                    ui.label("No floating selvedge needed.");
+                } else {
+                    ui.label(format!(
+                        "{} of {} picks miss an edge end.",
+                        misses.len(),
+                        draft.picks()
+                    ));
+                    ui.label(RichText::new("Use a floating selvedge on each side.").strong());
+                    for miss in misses.iter().take(LISTED) {
+                        ui.label(format!(
+                            "Row {} misses on the {}",
+                            miss.pick,
+                            side_name(miss.side)
+                        ));
+                    }
+                    if misses.len() > LISTED {
+                        ui.label(format!("and {} more", misses.len() - LISTED));
+                    }
+                }
+                ui.separator();
+                ui.checkbox(
+                    &mut self.selvedge_hints,
+                    "Show floating selvedge hints while weaving",
+                );
+            });
+        self.show_selvedge_window = open;
+    }
+
+    /// Which way round the floating selvedges the current pick goes.
+    pub(super) fn selvedge_hint(&self, ui: &mut egui::Ui, draft: &Draft) {
+        if !self.selvedge_hints || !draft.has_liftplan() {
+            return;
+        }
+        let current = self.picks_woven(draft) as usize;
+        if let Some(throw) = self.shuttle_throws(draft).get(current) {
+            ui.label(RichText::new(floating_hint(throw.from)).size(20.));
+        }
+    }
+}
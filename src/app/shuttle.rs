@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use eframe::egui::{self, Color32, ComboBox, Grid, RichText};
+
+use crate::{
+    draft::Draft,
+    loom::Side,
+    shuttle::{letter, side_name, throws, ShuttleSettings, Throw},
+};
+
+use super::{labelled_colour_block, MyApp};
+
+/// How far ahead to look for the next change of shuttle.
+const LOOKAHEAD: u32 = 50;
+
+impl MyApp {
+    /// The shuttles set up for the current draft.
+    pub(super) fn shuttles(&self) -> &ShuttleSettings {
+        static NONE: ShuttleSettings = ShuttleSettings {
+            first_side: Side::Left,
+            assignments: BTreeMap::new(),
+        };
+        self.shuttles.get(&self.draft_key()).unwrap_or(&NONE)
+    }
+
+    /// The shuttle carrying each of the draft's picks, pick 1 first.
+    pub(super) fn pick_shuttles(&self, draft: &Draft) -> Vec<u32> {
+        let weft = draft.weft_colours_used();
+        (1..=draft.picks())
+            .map(|pick| self.shuttles().shuttle(&weft, draft.weft_entry(pick)))
+            .collect()
+    }
+
+    /// Each pick of the piece so far, and a little way ahead, with the shuttle
+    /// that carries it.
+    pub(super) fn shuttle_throws(&self, draft: &Draft) -> Vec<Throw> {
+        let pick_shuttles = self.pick_shuttles(draft);
+        let upto = self.picks_woven(draft) + 1 + LOOKAHEAD;
+        let shuttles = pick_shuttles.iter().copied().cycle().take(upto as usize);
+        throws(shuttles, self.shuttles().first_side)
+    }
+
+    /// Which shuttle to throw now, when the next change is, and a warning if
+    /// the shuttle is on the wrong side.
+    pub(super) fn shuttle_banner(&self, ui: &mut egui::Ui, draft: &Draft) {
+        if self.shuttles().shuttles(&draft.weft_colours_used()) < 2 {
+            return;
+        }
+        let throws = self.shuttle_throws(draft);
+        let current = self.picks_woven(draft) as usize;
+        let Some(throw) = throws.get(current) else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!(
+                    "Shuttle {} from the {}",
+                    letter(throw.shuttle),
+                    side_name(throw.from)
+                ))
+                .size(24.),
+            );
+            let change = throws[current..]
+                .iter()
+                .position(|next| next.shuttle != throw.shuttle);
+            if let Some(picks) = change {
+                let next = throws[current + picks].shuttle;
+                ui.label(
+                    RichText::new(format!(
+                        "· change to shuttle {} in {picks} picks",
+                        letter(next)
+                    ))
+                    .size(24.),
+                );
+            }
+        });
+        if throw.wrong_side() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "Shuttle {} is on the {} but this pick should start from the {}",
+                    letter(throw.shuttle),
+                    side_name(throw.from),
+                    side_name(throw.expected)
+                ),
+            );
+        }
+    }
+
+    /// The letter of the shuttle carrying `pick`, if more than one is in use.
+    pub(super) fn shuttle_letter(&self, draft: &Draft, pick: u32) -> Option<char> {
+        let weft = draft.weft_colours_used();
+        let shuttles = self.shuttles();
+        (shuttles.shuttles(&weft) > 1)
+            .then(|| letter(shuttles.shuttle(&weft, draft.weft_entry(pick))))
+    }
+
+    /// Window for choosing which shuttle carries each weft colour.
+    pub(super) fn shuttles_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_shuttles_window;
+        egui::Window::new("Shuttles")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let weft = draft.weft_colours_used();
+                let mut settings = self.shuttles().clone();
+                ui.horizontal(|ui| {
+                    ui.label("First pick from the");
+                    ui.radio_value(&mut settings.first_side, Side::Left, "left");
+                    ui.radio_value(&mut settings.first_side, Side::Right, "right");
+                });
+                // Enough shuttles for a colour each, and any already chosen.
+                let choices = weft
+                    .iter()
+                    .map(|&entry| settings.shuttle(&weft, entry) + 1)
+                    .max()
+                    .unwrap_or(0)
+                    .max(weft.len() as u32);
+                Grid::new("shuttle assignments").show(ui, |ui| {
+                    for &entry in &weft {
+                        let [r, g, b] = draft.colour(entry);
+                        let colour = Color32::from_rgb(r, g, b);
+                        labelled_colour_block(ui, colour, &entry.to_string(), false);
+                        let mut shuttle = settings.shuttle(&weft, entry);
+                        ComboBox::from_id_salt(("shuttle", entry))
+                            .selected_text(format!("Shuttle {}", letter(shuttle)))
+                            .show_ui(ui, |ui| {
+                                for choice in 0..choices {
+                                    ui.selectable_value(
+                                        &mut shuttle,
+                                        choice,
+                                        format!("Shuttle {}", letter(choice)),
+                                    );
+                                }
+                            });
+                        if shuttle != settings.shuttle(&weft, entry) {
+                            settings.assignments.insert(entry, shuttle);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if settings != *self.shuttles() {
+                    self.shuttles.insert(self.draft_key(), settings);
+                }
+            });
+        self.show_shuttles_window = open;
+    }
+}
@@ -0,0 +1,122 @@
+use eframe::egui::{self, Color32, Layout, RichText, Vec2};
+use egui_extras::{Size, StripBuilder};
+
+use crate::draft::{bit, members, Draft, Mask};
+
+use super::{colour_block, row_text_size, text_block, MyApp};
+
+impl MyApp {
+    /// Treadling view for skeleton tie-ups, where some picks need two treadles
+    /// pressed together. Each pick shows which foot takes which treadle, and
+    /// the shafts the combined press lifts. Picks the tie-up can't make are
+    /// flagged.
+    pub(super) fn show_skeleton_treadling(
+        &mut self,
+        ui: &mut egui::Ui,
+        draft: &Draft,
+        last_row: u32,
+    ) {
+        let treadles = self.treadle_count(draft);
+        let shafts = draft.shafts;
+        if self.tieup_synthesis.is_none() && draft.tieup().is_empty() && !draft.has_liftplan() {
+            ui.label("This draft has no tie-up or liftplan, so the shafts lifted aren't known.");
+        }
+        ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        StripBuilder::new(ui)
+            .cell_layout(Layout::centered_and_justified(egui::Direction::LeftToRight))
+            .size(Size::exact(40.))
+            .size(Size::exact(80.))
+            .size(Size::exact(40.))
+            .size(Size::exact(20.))
+            .size(Size::exact(20.))
+            .vertical(|mut strip| {
+                for offset in [-1, 0, 1, 2, 3] {
+                    let pick = self.row as i32 + offset;
+                    if pick <= 0 || pick > last_row as i32 {
+                        strip.empty();
+                        continue;
+                    }
+
+                    let pick = pick as u32;
+                    let (left, right) = feet(self.treadles_pressed(draft, pick), treadles);
+                    let lifted = self.treadled_lift(draft, pick);
+                    let unreproducible = self
+                        .tieup_synthesis
+                        .as_ref()
+                        .is_some_and(|synthesis| synthesis.unreproducible.contains(&pick));
+                    strip.strip(|sb| {
+                        sb.size(Size::exact(20.))
+                            .sizes(Size::relative(0.2), 2)
+                            .sizes(Size::relative(0.5 / shafts as f32), shafts as usize)
+                            .horizontal(|mut strip| {
+                                let color = draft.weft_colour(pick);
+                                let color = Color32::from_rgb(color[0], color[1], color[2]);
+                                strip.cell(|ui| {
+                                    colour_block(ui, color, offset == 0);
+                                });
+
+                                for (foot, pressed) in [("L", &left), ("R", &right)] {
+                                    if unreproducible {
+                                        strip.cell(|ui| {
+                                            ui.colored_label(
+                                                ui.visuals().warn_fg_color,
+                                                RichText::new(format!("{foot} ✖"))
+                                                    .size(row_text_size(offset)),
+                                            )
+                                            .on_hover_text("The tie-up can't make this shed");
+                                        });
+                                        continue;
+                                    }
+                                    let treadles = if pressed.is_empty() {
+                                        "-".to_string()
+                                    } else {
+                                        pressed
+                                            .iter()
+                                            .map(|treadle| treadle.to_string())
+                                            .collect::<Vec<_>>()
+                                            .join("+")
+                                    };
+                                    strip.cell(|ui| {
+                                        text_block(
+                                            ui,
+                                            RichText::new(format!("{foot} {treadles}"))
+                                                .size(row_text_size(offset)),
+                                            offset == 0,
+                                            !pressed.is_empty(),
+                                        );
+                                    });
+                                }
+
+                                for shaft in self.loom.orientation.shaft_order(shafts) {
+                                    strip.cell(|ui| {
+                                        text_block(
+                                            ui,
+                                            RichText::new(shaft.to_string())
+                                                .size(row_text_size(offset) / 2.),
+                                            offset == 0,
+                                            lifted & bit(shaft) != 0,
+                                        );
+                                    });
+                                }
+                            });
+                    });
+                }
+            });
+    }
+}
+
+/// Splits the treadles pressed on a pick between the feet, left foot on the
+/// lower-numbered treadles. A lone treadle goes to whichever foot is on that
+/// side of the loom.
+fn feet(pressed: Mask, treadles: u32) -> (Vec<u32>, Vec<u32>) {
+    let pressed: Vec<u32> = members(pressed).collect();
+    match pressed[..] {
+        [] => (vec![], vec![]),
+        [only] if only <= treadles.div_ceil(2) => (vec![only], vec![]),
+        [only] => (vec![], vec![only]),
+        _ => {
+            let (left, right) = pressed.split_at(pressed.len().div_ceil(2));
+            (left.to_vec(), right.to_vec())
+        }
+    }
+}
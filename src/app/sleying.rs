@@ -0,0 +1,108 @@
+use eframe::egui::{self, DragValue, Layout, RichText, Vec2};
+use egui_extras::{Size, StripBuilder};
+
+use crate::{draft::Draft, loom::Side, sleying::DentingPlan};
+
+use super::{row_text_size, text_block, MyApp};
+
+impl MyApp {
+    /// Sleying the reed: which ends go through each dent, one dent at a time
+    /// from the side we thread from.
+    pub(super) fn show_sleying(&mut self, ui: &mut egui::Ui, draft: &Draft, denting: &DentingPlan) {
+        let orientation = self.loom.orientation;
+        let side = match orientation.start {
+            Side::Left => "left",
+            Side::Right => "right",
+        };
+
+        ui.horizontal(|ui| {
+            let mut epi = self.epi(draft);
+            ui.label("Ends per inch");
+            if ui
+                .add(DragValue::new(&mut epi).range(1.0..=200.0).speed(0.5))
+                .changed()
+            {
+                self.epi = Some(epi);
+            }
+            if self.epi.is_some()
+                && draft.warp_spacing.is_some()
+                && ui.button("Use draft's").clicked()
+            {
+                self.epi = None;
+            }
+        });
+        let pattern: Vec<_> = denting.pattern().iter().map(u32::to_string).collect();
+        ui.label(format!(
+            "Sley {} in a {}-dent reed, {} dents, {:.1}\" wide",
+            pattern.join(", "),
+            self.loom.dents_per_inch,
+            denting.dents.len(),
+            denting.width
+        ));
+        let skipped = denting.skipped();
+        if skipped > 0 {
+            ui.label(format!("{skipped} dents left empty"));
+        }
+        ui.label(format!(
+            "Start at dent {} from the {side} of the reed to centre the warp",
+            denting.first_dent
+        ));
+        if denting.unsleyed > 0 {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "The warp doesn't fit the reed: {} ends are left over",
+                    denting.unsleyed
+                ),
+            );
+        }
+        ui.separator();
+
+        let ends_in = |dent: i32| -> Option<String> {
+            let steps = denting.dents.get(usize::try_from(dent - 1).ok()?)?;
+            if steps.is_empty() {
+                return Some("skip".to_string());
+            }
+            let ends: Vec<_> = steps
+                .clone()
+                .map(|step| {
+                    orientation
+                        .end_at_step(step as i32, draft.ends())
+                        .to_string()
+                })
+                .collect();
+            Some(ends.join(", "))
+        };
+
+        let dent = self.sley_step.max(1) as i32;
+        ui.label(
+            RichText::new(format!(
+                "Reed dent {}",
+                denting.first_dent as i32 + dent - 1
+            ))
+            .size(32.),
+        );
+        ui.spacing_mut().item_spacing = Vec2::new(3., 3.);
+        let offsets = if orientation.start == Side::Left {
+            (-3..=3).collect::<Vec<_>>()
+        } else {
+            (-3..=3).rev().collect()
+        };
+        StripBuilder::new(ui)
+            .cell_layout(Layout::centered_and_justified(egui::Direction::TopDown))
+            .sizes(Size::relative(1. / offsets.len() as f32), offsets.len())
+            .horizontal(|mut strip| {
+                for offset in offsets {
+                    strip.cell(|ui| {
+                        let text = ends_in(dent + offset).unwrap_or_default();
+                        text_block(
+                            ui,
+                            RichText::new(text).size(row_text_size(offset) / 2.),
+                            offset == 0,
+                            false,
+                        );
+                    });
+                }
+            });
+    }
+}
@@ -0,0 +1,97 @@
+use eframe::egui::{self, Button, Grid, RichText};
+
+use crate::{
+    draft::{bit, Draft},
+    tieup::synthesize,
+};
+
+use super::MyApp;
+
+impl MyApp {
+    /// Window for converting a dobby liftplan into a tie-up and treadling.
+    /// The search can take a while, so it runs off the UI thread.
+    pub(super) fn tieup_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        if let Some(synthesis) = self.tieup_conversion.write().unwrap().take() {
+            self.tieup_synthesis = Some(synthesis);
+            self.tieup_converting = false;
+        }
+        let mut open = self.show_tieup_window;
+        egui::Window::new("Liftplan to treadling")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "The loom has {} treadles. Two may be pressed at once.",
+                    self.loom.treadles
+                ));
+                ui.horizontal(|ui| {
+                    let convert = ui.add_enabled(!self.tieup_converting, Button::new("Convert"));
+                    if convert.clicked() {
+                        self.convert_liftplan(ctx, draft);
+                    }
+                    if self.tieup_converting {
+                        ui.spinner();
+                        ui.label("Looking for a tie-up…");
+                    } else if self.tieup_synthesis.is_some() && ui.button("Discard").clicked() {
+                        self.tieup_synthesis = None;
+                    }
+                });
+
+                let Some(synthesis) = &self.tieup_synthesis else {
+                    ui.label("Treadling mode uses the draft's own treadling.");
+                    return;
+                };
+                let fewest = if synthesis.fewest {
+                    "the fewest possible"
+                } else {
+                    "the fewest found, though fewer may do"
+                };
+                ui.label(format!(
+                    "Tie-up uses {} treadles, {fewest}. Treadling mode now follows it.",
+                    synthesis.treadles()
+                ));
+                Grid::new("synthesized tieup").show(ui, |ui| {
+                    ui.label("");
+                    for treadle in 1..=synthesis.treadles() {
+                        ui.label(RichText::new(treadle.to_string()).strong());
+                    }
+                    ui.end_row();
+                    for shaft in (1..=draft.shafts).rev() {
+                        ui.label(RichText::new(shaft.to_string()).strong());
+                        for &tied in &synthesis.tieup {
+                            ui.label(if tied & bit(shaft) != 0 { "■" } else { "·" });
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                if synthesis.unreproducible.is_empty() {
+                    ui.label("Every pick can be woven.");
+                } else {
+                    let picks: Vec<_> = synthesis
+                        .unreproducible
+                        .iter()
+                        .map(|pick| pick.to_string())
+                        .collect();
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!("These picks can't be woven: {}", picks.join(", ")),
+                    );
+                }
+            });
+        self.show_tieup_window = open;
+    }
+
+    /// Starts looking for a tie-up for `draft`'s liftplan in the background.
+    fn convert_liftplan(&mut self, ctx: &egui::Context, draft: &Draft) {
+        self.tieup_converting = true;
+        let liftplan = draft.liftplan().to_vec();
+        let treadles = self.loom.treadles;
+        let conversion = self.tieup_conversion.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let synthesis = synthesize(&liftplan, treadles);
+            *conversion.write().unwrap() = Some(synthesis);
+            ctx.request_repaint();
+        });
+    }
+}
@@ -0,0 +1,94 @@
+use eframe::egui::{self, Button, DragValue, RichText};
+
+use crate::{
+    draft::Draft,
+    unweave::{last_woven, picks_to_unweave},
+};
+
+use super::{count_down, MyApp, OperationMode};
+
+impl MyApp {
+    /// Whether the current mode weaves row by row, so can be unwoven.
+    pub(super) fn can_unweave(&self) -> bool {
+        self.weaving_mode() && self.mode != OperationMode::Drawloom
+    }
+
+    /// The current row, counted through the whole piece rather than the
+    /// repeat. It isn't woven yet.
+    fn piece_pick(&self, picks: u32) -> u32 {
+        (self.repeat.max(1) - 1) * picks + self.row
+    }
+
+    /// Woven rows left to unweave, from the one before the current row down
+    /// to the mistake.
+    pub(super) fn rows_to_unweave(&self, picks: u32) -> Option<u32> {
+        let mistake = self.mistake.filter(|_| self.unweaving)?;
+        Some(picks_to_unweave(self.piece_pick(picks), mistake))
+    }
+
+    /// Marking a mistake, and starting or stopping unweaving back to it.
+    pub(super) fn mistake_controls(&mut self, ui: &mut egui::Ui, draft: &Draft) {
+        let picks = draft.picks().max(1);
+        match self.mistake {
+            None => {
+                let last = last_woven(self.piece_pick(picks));
+                let mark = ui.add_enabled(last.is_some(), Button::new("Mark mistake"));
+                if mark.on_hover_text("Marks the last row woven").clicked() {
+                    self.mistake = last;
+                }
+            }
+            Some(mut mistake) => {
+                ui.horizontal(|ui| {
+                    ui.label("Mistake at row");
+                    let mut row = (mistake - 1) % picks + 1;
+                    if ui.add(DragValue::new(&mut row).range(1..=picks)).changed() {
+                        mistake = mistake - (mistake - 1) % picks - 1 + row;
+                    }
+                    ui.label(format!("of repeat {}", (mistake - 1) / picks + 1));
+                });
+                // The mistake can't be a row that isn't woven yet.
+                let last = last_woven(self.piece_pick(picks)).unwrap_or(1);
+                self.mistake = Some(mistake.min(last));
+                ui.horizontal(|ui| {
+                    if !self.unweaving && ui.button("Unweave").clicked() {
+                        self.unweaving = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.mistake = None;
+                        self.unweaving = false;
+                    }
+                });
+            }
+        }
+    }
+
+    /// Takes out the last row woven. Once the marked row is out, weaving
+    /// carries on forwards from it.
+    pub(super) fn unweave_row(&mut self, last_row: u32) {
+        let Some(mistake) = self.mistake else {
+            return;
+        };
+        if picks_to_unweave(self.piece_pick(last_row), mistake) > 0 {
+            let before = self.row;
+            count_down(&mut self.row, last_row);
+            if self.row > before {
+                self.repeat = self.repeat.saturating_sub(1).max(1);
+            }
+        }
+        if picks_to_unweave(self.piece_pick(last_row), mistake) == 0 {
+            self.mistake = None;
+            self.unweaving = false;
+        }
+    }
+
+    /// Heading for the liftplan while unweaving.
+    pub(super) fn unweaving_banner(&self, ui: &mut egui::Ui, picks: u32) {
+        if let Some(rows) = self.rows_to_unweave(picks) {
+            ui.label(
+                RichText::new(format!("Unweaving: {rows} rows to take out"))
+                    .size(24.)
+                    .color(ui.visuals().warn_fg_color),
+            );
+        }
+    }
+}
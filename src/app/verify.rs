@@ -0,0 +1,131 @@
+use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, Vec2};
+
+use crate::{
+    draft::{bit, Draft},
+    loom::Side,
+    motif::shafts_label,
+};
+
+use super::MyApp;
+
+/// How many ends either side of the one being looked at the drawdown shows.
+const DRAWDOWN_SPAN: u32 = 12;
+const DRAWDOWN_PICKS: u32 = 24;
+
+impl MyApp {
+    /// Threading check: step through the ends, confirming or correcting the
+    /// shafts each one is really on.
+    pub(super) fn show_verification(&mut self, ui: &mut egui::Ui, draft: &Draft) {
+        let end = self.check_end.clamp(1, draft.ends().max(1));
+        let expected = self.verification.expected(end);
+        let actual = self.verification.actual(end);
+
+        ui.label(RichText::new(format!("End {end}")).size(32.));
+        ui.label(format!("Draft: shaft {}", shafts_label(expected)));
+        ui.horizontal(|ui| {
+            ui.label("Actually on:");
+            for shaft in self.loom.orientation.shaft_order(draft.shafts) {
+                let on = actual & bit(shaft) != 0;
+                let button = ui.selectable_label(on, RichText::new(shaft.to_string()).size(24.));
+                if button.clicked() {
+                    self.verification.record(end, actual ^ bit(shaft));
+                }
+            }
+            if ui.button("As drafted").clicked() {
+                self.verification.record(end, expected);
+            }
+        });
+        match self.verification.found(end) {
+            None => ui.label("Not checked yet. Next confirms it's as drafted."),
+            Some(found) if found == expected => ui.label("✔ Checked"),
+            Some(found) => ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!(
+                    "Mismatch: on shaft {}, should be on shaft {}",
+                    shafts_label(found),
+                    shafts_label(expected)
+                ),
+            ),
+        };
+
+        let corrections = self.verification.corrections();
+        ui.separator();
+        ui.label(format!(
+            "{} of {} ends checked, {} to correct",
+            self.verification.checked(),
+            draft.ends(),
+            corrections.len()
+        ));
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.set_width(280.);
+                ScrollArea::vertical()
+                    .id_salt("corrections")
+                    .max_height(240.)
+                    .show(ui, |ui| {
+                        for correction in &corrections {
+                            let spoilt = self.verification.spoilt_picks(draft, correction.end);
+                            ui.horizontal(|ui| {
+                                if ui.small_button("Go").clicked() {
+                                    self.check_end = correction.end;
+                                }
+                                ui.label(format!(
+                                    "End {}: shaft {} → {} ({} picks wrong)",
+                                    correction.end,
+                                    shafts_label(correction.found),
+                                    shafts_label(correction.expected),
+                                    spoilt.len()
+                                ));
+                            });
+                        }
+                    });
+                if ui.button("Start again").clicked() {
+                    self.verification = crate::verify::Verification::new(draft);
+                }
+            });
+            self.verification_drawdown(ui, draft, end);
+        });
+    }
+
+    /// The drawdown around `end` as it's actually threaded, with the cells a
+    /// threading mistake changes picked out in red.
+    fn verification_drawdown(&self, ui: &mut egui::Ui, draft: &Draft, end: u32) {
+        let ends = draft.ends();
+        let picks = (draft.liftplan().len() as u32).min(DRAWDOWN_PICKS);
+        let first = end.saturating_sub(DRAWDOWN_SPAN).max(1);
+        let last = (end + DRAWDOWN_SPAN).min(ends);
+        if picks == 0 || first > last {
+            return;
+        }
+        let cell = 12.;
+        let size = Vec2::new((last - first + 1) as f32, picks as f32) * cell;
+        let (response, painter) = ui.allocate_painter(size, Sense::hover());
+        let origin = response.rect.min;
+        for e in first..=last {
+            // End 1 sits on whichever side of the loom the orientation says.
+            let column = if self.loom.orientation.end_one == Side::Left {
+                e - first
+            } else {
+                last - e
+            };
+            let expected = self.verification.expected(e);
+            let actual = self.verification.actual(e);
+            for pick in 1..=picks {
+                let lifted = draft.lift(pick);
+                let up = lifted & actual != 0;
+                let wrong = up != (lifted & expected != 0);
+                let fill = match (wrong, up) {
+                    (true, _) => Color32::RED,
+                    (false, true) => Color32::DARK_GRAY,
+                    (false, false) => Color32::LIGHT_GRAY,
+                };
+                let min = origin + Vec2::new(column as f32, (pick - 1) as f32) * cell;
+                let rect = egui::Rect::from_min_size(min, Vec2::splat(cell));
+                painter.rect_filled(rect, 0., fill);
+                if e == end {
+                    painter.rect_stroke(rect, 0., Stroke::new(1., Color32::YELLOW));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use eframe::egui::{self, Color32, RichText, Sense, Stroke, Vec2};
+
+use crate::{
+    draft::Draft,
+    weft_runs::{position, runs},
+};
+
+use super::{colour_block, MyApp};
+
+impl MyApp {
+    /// How long the current weft colour lasts, what comes next, and a bar
+    /// of every run in the treadling with the current pick marked.
+    pub(super) fn weft_runs(&self, ui: &mut egui::Ui, draft: &Draft) {
+        let runs = runs(draft);
+        if runs.len() < 2 {
+            return;
+        }
+        let Some((left, next)) = position(&runs, self.row) else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label(format!("{left} picks left in this colour, then"));
+            let [r, g, b] = next.colour;
+            colour_block(ui, Color32::from_rgb(r, g, b), false);
+            ui.label(format!("for {} picks", next.picks));
+        });
+
+        let picks = draft.picks() as f32;
+        let (response, painter) =
+            ui.allocate_painter(Vec2::new(ui.available_width(), 16.), Sense::hover());
+        let rect = response.rect;
+        let x = |pick: u32| rect.left() + rect.width() * (pick - 1) as f32 / picks;
+        for run in &runs {
+            let [r, g, b] = run.colour;
+            let run_rect = egui::Rect::from_x_y_ranges(
+                x(run.first_pick)..=x(run.first_pick + run.picks),
+                rect.y_range(),
+            );
+            painter.rect_filled(run_rect, 0., Color32::from_rgb(r, g, b));
+        }
+        painter.rect_stroke(rect, 0., Stroke::new(1., Color32::DARK_GRAY));
+        let marker = x(self.row) + rect.width() / picks / 2.;
+        painter.vline(marker, rect.y_range(), Stroke::new(3., Color32::BLACK));
+        painter.vline(marker, rect.y_range(), Stroke::new(1., Color32::WHITE));
+        response.on_hover_text(RichText::new(format!("Row {}", self.row)));
+    }
+}
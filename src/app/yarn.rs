@@ -0,0 +1,154 @@
+use eframe::egui::{self, color_picker, Color32, ComboBox, DragValue, Grid, RichText, TextEdit};
+
+use crate::{
+    calculator::estimate,
+    draft::Draft,
+    yarn::{usage, Yarn, YarnUsage},
+};
+
+use super::{labelled_colour_block, MyApp};
+
+impl MyApp {
+    /// Picks woven so far, counting earlier repeats of the treadling.
+    pub(super) fn picks_woven(&self, draft: &Draft) -> u32 {
+        (self.repeat.max(1) - 1) * draft.picks() + self.row.saturating_sub(1)
+    }
+
+    /// How much of each yarn in the stash the project uses.
+    pub(super) fn yarn_usage(&self, draft: &Draft) -> Vec<YarnUsage> {
+        if self.yarns.yarns.is_empty() {
+            return vec![];
+        }
+        let needs = estimate(draft, &self.project, self.epi(draft), self.ppi(draft));
+        usage(
+            &self.yarns,
+            &self.draft_key(),
+            draft,
+            &needs,
+            self.picks_woven(draft),
+        )
+    }
+
+    /// Warnings for any yarn that won't last the piece.
+    pub(super) fn yarn_warnings(&self, ui: &mut egui::Ui, usage: &[YarnUsage], draft: &Draft) {
+        let repeat = draft.picks().max(1);
+        for yarn_usage in usage {
+            let name = &self.yarns.yarns[yarn_usage.yarn].name;
+            match yarn_usage.runs_out_at {
+                Some(0) => {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!("Not enough {name} for the warp"),
+                    );
+                }
+                Some(pick) => {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!(
+                            "{name} runs out at row {} of repeat {}",
+                            (pick - 1) % repeat + 1,
+                            (pick - 1) / repeat + 1
+                        ),
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Window for keeping track of the yarn on hand and which colours of the
+    /// draft it's used for. Usage is worked out after any yarn is removed
+    /// or assigned, so it never refers to a yarn that's gone.
+    pub(super) fn yarn_window(&mut self, ctx: &egui::Context, draft: &Draft) {
+        let mut open = self.show_yarn_window;
+        egui::Window::new("Yarn stash")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let mut remove = None;
+                Grid::new("yarns").striped(true).show(ui, |ui| {
+                    for heading in ["Name", "Fibre", "Yd/lb", "Colour", "On hand", ""] {
+                        ui.label(RichText::new(heading).strong());
+                    }
+                    ui.end_row();
+                    for (i, yarn) in self.yarns.yarns.iter_mut().enumerate() {
+                        ui.add(TextEdit::singleline(&mut yarn.name).desired_width(120.));
+                        ui.add(TextEdit::singleline(&mut yarn.fibre).desired_width(80.));
+                        ui.add(DragValue::new(&mut yarn.yards_per_pound).range(1.0..=50000.0));
+                        color_picker::color_edit_button_srgb(ui, &mut yarn.colour);
+                        ui.add(
+                            DragValue::new(&mut yarn.on_hand)
+                                .range(0.0..=1000.0)
+                                .speed(0.05)
+                                .suffix(" lb"),
+                        );
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = remove {
+                    self.yarns.remove(i);
+                }
+                if ui.button("Add yarn").clicked() {
+                    self.yarns.yarns.push(Yarn::default());
+                }
+
+                ui.separator();
+                ui.label(RichText::new("Draft colours").strong());
+                let key = self.draft_key();
+                Grid::new("colour assignments").show(ui, |ui| {
+                    for entry in draft.colours_used() {
+                        let [r, g, b] = draft.colour(entry);
+                        let colour = Color32::from_rgb(r, g, b);
+                        labelled_colour_block(ui, colour, &entry.to_string(), false);
+                        let mut assigned = self.yarns.yarn_for(&key, entry);
+                        let selected = assigned
+                            .map(|yarn| self.yarns.yarns[yarn].name.clone())
+                            .unwrap_or_else(|| "None".to_string());
+                        ComboBox::from_id_salt(("yarn for", entry))
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut assigned, None, "None");
+                                for (i, yarn) in self.yarns.yarns.iter().enumerate() {
+                                    ui.selectable_value(&mut assigned, Some(i), &yarn.name);
+                                }
+                            });
+                        if assigned != self.yarns.yarn_for(&key, entry) {
+                            self.yarns.assign(&key, entry, assigned);
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                let usage = self.yarn_usage(draft);
+                ui.separator();
+                ui.label(format!(
+                    "{} picks woven, following the warp calculator's plan",
+                    self.picks_woven(draft)
+                ));
+                Grid::new("yarn usage").striped(true).show(ui, |ui| {
+                    for heading in ["Yarn", "Warp", "Used", "Needed", "On hand"] {
+                        ui.label(RichText::new(heading).strong());
+                    }
+                    ui.end_row();
+                    for yarn_usage in &usage {
+                        let yarn = &self.yarns.yarns[yarn_usage.yarn];
+                        ui.label(&yarn.name);
+                        ui.label(format!("{:.0} yd", yarn_usage.warp));
+                        ui.label(format!("{:.0} yd", yarn_usage.used()));
+                        ui.label(format!("{:.0} yd", yarn_usage.total()));
+                        let on_hand = format!("{:.0} yd", yarn.yards_on_hand());
+                        if yarn_usage.runs_out_at.is_some() {
+                            ui.colored_label(ui.visuals().warn_fg_color, on_hand);
+                        } else {
+                            ui.label(on_hand);
+                        }
+                        ui.end_row();
+                    }
+                });
+                self.yarn_warnings(ui, &usage, draft);
+            });
+        self.show_yarn_window = open;
+    }
+}
@@ -0,0 +1,123 @@
+//! Working out how much yarn a project needs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::draft::Draft;
+
+const INCHES_PER_YARD: f32 = 36.;
+
+/// What the weaver wants to end up with, and how much the cloth loses on
+/// the way there.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Project {
+    /// Length of the finished cloth, in inches.
+    pub finished_length: f32,
+    /// Warp lost to tying on, the front and back of the loom, and fringe, in inches.
+    pub loom_waste: f32,
+    /// Fraction lost to the threads bending over and under each other.
+    pub take_up: f32,
+    /// Fraction lost in wet finishing.
+    pub shrinkage: f32,
+    pub warp_yards_per_pound: f32,
+    pub weft_yards_per_pound: f32,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            finished_length: 72.,
+            loom_waste: 27.,
+            take_up: 0.1,
+            shrinkage: 0.1,
+            warp_yards_per_pound: 840.,
+            weft_yards_per_pound: 840.,
+        }
+    }
+}
+
+/// How much of one colour of yarn is needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColourNeed {
+    /// The colour's entry in the draft's colour table.
+    pub entry: u32,
+    pub colour: [u8; 3],
+    /// Ends in the warp, or picks in the cloth.
+    pub threads: u32,
+    pub yards: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct YarnNeeds {
+    /// Length of warp to wind, in inches.
+    pub warp_length: f32,
+    /// Length of cloth to weave, measured on the loom, in inches.
+    pub woven_length: f32,
+    /// Width of the warp in the reed, in inches.
+    pub width: f32,
+    pub picks: u32,
+    /// Length of weft in each pick, in inches.
+    pub pick_length: f32,
+    pub warp: Vec<ColourNeed>,
+    pub weft: Vec<ColourNeed>,
+}
+
+impl YarnNeeds {
+    pub fn warp_yards(&self) -> f32 {
+        self.warp.iter().map(|need| need.yards).sum()
+    }
+
+    pub fn weft_yards(&self) -> f32 {
+        self.weft.iter().map(|need| need.yards).sum()
+    }
+}
+
+/// Works out the yarn for weaving `project` from `draft` at `epi` ends and
+/// `ppi` picks per inch. The treadling repeats for as long as it takes.
+pub fn estimate(draft: &Draft, project: &Project, epi: f32, ppi: f32) -> YarnNeeds {
+    let kept = (1. - project.take_up.clamp(0., 0.9)) * (1. - project.shrinkage.clamp(0., 0.9));
+    let woven_length = project.finished_length / kept;
+    let warp_length = woven_length + project.loom_waste;
+    let width = draft.ends() as f32 / epi.max(0.1);
+    let picks = (woven_length * ppi).round() as u32;
+    // The weft takes up across the cloth as the warp does along it.
+    let pick_length = width * (1. + project.take_up);
+
+    let mut warp = tally(draft, (1..=draft.ends()).map(|end| draft.warp_entry(end)));
+    for need in &mut warp {
+        need.yards = need.threads as f32 * warp_length / INCHES_PER_YARD;
+    }
+    let repeat = draft.picks().max(1);
+    let mut weft = tally(draft, (0..picks).map(|i| draft.weft_entry(i % repeat + 1)));
+    for need in &mut weft {
+        need.yards = need.threads as f32 * pick_length / INCHES_PER_YARD;
+    }
+
+    YarnNeeds {
+        warp_length,
+        woven_length,
+        width,
+        picks,
+        pick_length,
+        warp,
+        weft,
+    }
+}
+
+/// Counts the threads in each colour table entry, in order of first
+/// appearance.
+fn tally(draft: &Draft, entries: impl Iterator<Item = u32>) -> Vec<ColourNeed> {
+    let mut needs: Vec<ColourNeed> = vec![];
+    for entry in entries {
+        match needs.iter_mut().find(|need| need.entry == entry) {
+            Some(need) => need.threads += 1,
+            None => needs.push(ColourNeed {
+                entry,
+                colour: draft.colour(entry),
+                threads: 1,
+                yards: 0.,
+            }),
+        }
+    }
+    needs
+}
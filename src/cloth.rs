@@ -0,0 +1,220 @@
+//! Drawing what the woven cloth will look like, thread by thread.
+
+use crate::draft::Draft;
+
+/// The largest image we'll draw, in pixels each way.
+pub const MAX_SIZE: usize = 2048;
+
+/// The colour that shows through the gaps between threads.
+const GAP: [u8; 3] = [24, 24, 24];
+
+/// One thread's share of the cloth, in inches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thread {
+    /// From the start of this thread's space to the next one's.
+    pub spacing: f32,
+    pub thickness: f32,
+}
+
+/// How the ends and picks are laid out, end 1 and pick 1 first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sett {
+    pub warp: Vec<Thread>,
+    pub weft: Vec<Thread>,
+}
+
+impl Sett {
+    /// Each thread's spacing and thickness as the draft gives them, thread
+    /// by thread where it can, and otherwise from `epi` and `ppi`, with
+    /// threads taking up most of the space between them.
+    pub fn new(draft: &Draft, epi: f32, ppi: f32) -> Self {
+        let thread = |spacing: Option<f32>, thickness: Option<f32>, per_inch: f32| {
+            let spacing = spacing.unwrap_or(1. / per_inch.max(1.));
+            Thread {
+                spacing,
+                thickness: thickness.unwrap_or(spacing * 0.8),
+            }
+        };
+        Self {
+            warp: (1..=draft.ends())
+                .map(|end| thread(draft.end_spacing(end), draft.end_thickness(end), epi))
+                .collect(),
+            weft: (1..=draft.picks())
+                .map(|pick| thread(draft.pick_spacing(pick), draft.pick_thickness(pick), ppi))
+                .collect(),
+        }
+    }
+}
+
+/// An RGBA image of the cloth, as many ends and picks as fit.
+pub struct Cloth {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+/// Threads laid side by side, each starting where the last one's space ends.
+struct Layout<'a> {
+    threads: &'a [Thread],
+    starts: Vec<f32>,
+}
+
+impl<'a> Layout<'a> {
+    fn new(threads: &'a [Thread]) -> Self {
+        let starts = threads
+            .iter()
+            .scan(0., |start, thread| {
+                let this = *start;
+                *start += thread.spacing;
+                Some(this)
+            })
+            .collect();
+        Self { threads, starts }
+    }
+
+    fn width(&self) -> f32 {
+        self.threads.iter().map(|thread| thread.spacing).sum()
+    }
+
+    /// Where `position` falls among the threads: the thread's index from 0,
+    /// how far it is from the thread's centre as a fraction of the thread's
+    /// half-width (above 1 is in the gap), and how far along the thread's
+    /// space it is, from -1 at the start to 1 at the end.
+    fn across(&self, position: f32) -> (u32, f32, f32) {
+        let index = self
+            .starts
+            .partition_point(|&start| start <= position)
+            .saturating_sub(1);
+        let thread = self.threads[index];
+        let along = (position - self.starts[index]) / thread.spacing;
+        let offset = (along - 0.5).abs() * thread.spacing / (thread.thickness / 2.);
+        (index as u32, offset, along * 2. - 1.)
+    }
+}
+
+/// Draws the cloth at `pixels_per_inch`, end 1 on the left and pick 1 at
+/// the bottom as it comes off the loom.
+pub fn render(draft: &Draft, sett: &Sett, pixels_per_inch: f32) -> Cloth {
+    let (ends, picks) = (draft.ends(), draft.picks().max(1));
+    let (warp, weft) = (Layout::new(&sett.warp), Layout::new(&sett.weft));
+    let (width, height) = if sett.warp.is_empty() || sett.weft.is_empty() {
+        (0, 0)
+    } else {
+        (
+            ((warp.width() * pixels_per_inch) as usize).min(MAX_SIZE),
+            ((weft.width() * pixels_per_inch) as usize).min(MAX_SIZE),
+        )
+    };
+    let warp_up = |end: u32, pick: u32| {
+        let pick = (pick + picks - 1) % picks + 1;
+        draft.lift(pick) & draft.threading(end) != 0
+    };
+
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        let inches = (height - 1 - y) as f32 / pixels_per_inch;
+        // How far along the pick's space we are says which way the warp dips.
+        let (pick, weft_offset, along_warp) = weft.across(inches);
+        let pick = pick + 1;
+        for x in 0..width {
+            let inches = x as f32 / pixels_per_inch;
+            let (end, warp_offset, along_weft) = warp.across(inches);
+            let end = end + 1;
+            let in_warp = warp_offset < 1.;
+            let in_weft = weft_offset < 1.;
+
+            let up = warp_up(end, pick);
+            let colour = match (in_warp, in_weft) {
+                (false, false) => GAP,
+                (true, false) => shade(draft.warp_colour(end), warp_offset, 1.),
+                (false, true) => shade(draft.weft_colour(pick), weft_offset, 1.),
+                (true, true) if up => {
+                    // The warp dips where it goes under the next or last pick.
+                    let next = if along_warp > 0. {
+                        pick + 1
+                    } else {
+                        pick + picks - 1
+                    };
+                    let dip = if warp_up(end, next) {
+                        0.
+                    } else {
+                        along_warp.abs()
+                    };
+                    shade(draft.warp_colour(end), warp_offset, dip)
+                }
+                (true, true) => {
+                    // And the weft where it goes under the next or last end.
+                    let next = if along_weft > 0. { end + 1 } else { end - 1 };
+                    let over = next == 0 || next > ends || !warp_up(next, pick);
+                    let dip = if over { 0. } else { along_weft.abs() };
+                    shade(draft.weft_colour(pick), weft_offset, dip)
+                }
+            };
+            pixels.extend_from_slice(&[colour[0], colour[1], colour[2], 255]);
+        }
+    }
+    Cloth {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// Lights a thread as a cylinder, brightest along its centre, and darker
+/// still where it dips under a crossing thread (`dip` 1 at the deepest).
+fn shade(colour: [u8; 3], offset: f32, dip: f32) -> [u8; 3] {
+    let round = (1. - offset * offset).max(0.).sqrt();
+    let light = (0.55 + 0.45 * round) * (1. - 0.3 * dip.clamp(0., 1.).powi(2));
+    colour.map(|channel| (channel as f32 * light).min(255.) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draft::bit;
+
+    fn thread(spacing: f32) -> Thread {
+        Thread {
+            spacing,
+            thickness: spacing * 0.8,
+        }
+    }
+
+    #[test]
+    fn lays_out_thick_and_thin_threads() {
+        let threads = [thread(0.1), thread(0.3), thread(0.1)];
+        let layout = Layout::new(&threads);
+        assert!((layout.width() - 0.5).abs() < 1e-6);
+        assert_eq!(layout.across(0.05).0, 0);
+        let (index, offset, along) = layout.across(0.25);
+        assert_eq!(index, 1);
+        assert!(offset < 0.01 && along.abs() < 0.01);
+        // The edge of the thick thread's space is in the gap.
+        assert!(layout.across(0.11).1 > 1.);
+        assert_eq!(layout.across(0.45).0, 2);
+    }
+
+    #[test]
+    fn renders_at_the_sett() {
+        let draft = Draft::for_test(2, &[bit(1), bit(2), bit(1)], &[bit(1), bit(2)]);
+        let sett = Sett {
+            warp: vec![thread(0.1), thread(0.3), thread(0.1)],
+            weft: vec![thread(0.1), thread(0.1)],
+        };
+        let cloth = render(&draft, &sett, 100.);
+        assert_eq!((cloth.width, cloth.height), (50, 20));
+        assert_eq!(cloth.pixels.len(), 50 * 20 * 4);
+
+        let empty = Sett::new(&Draft::for_test(2, &[], &[]), 12., 12.);
+        let cloth = render(&Draft::for_test(2, &[], &[]), &empty, 100.);
+        assert_eq!((cloth.width, cloth.height), (0, 0));
+    }
+
+    #[test]
+    fn falls_back_to_the_uniform_sett() {
+        let draft = Draft::for_test(2, &[bit(1), bit(2)], &[bit(1)]);
+        let sett = Sett::new(&draft, 10., 20.);
+        assert_eq!(sett.warp, vec![thread(0.1); 2]);
+        assert_eq!(sett.weft, vec![thread(0.05)]);
+    }
+}
@@ -0,0 +1,184 @@
+//! Colourways: other colourings of a draft, kept beside it rather than
+//! written into the WIF.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::draft::Draft;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Colourway {
+    pub name: String,
+    /// Colours to use in place of the draft's, by colour table entry.
+    /// Entries past the end of the draft's table are colours added here.
+    pub replacements: BTreeMap<u32, [u8; 3]>,
+    /// Colour table entries to repeat across the ends from end 1, instead
+    /// of the draft's sequence. Empty keeps the draft's.
+    pub warp: Vec<u32>,
+    /// Colour table entries to repeat up the picks from pick 1, likewise.
+    pub weft: Vec<u32>,
+}
+
+impl Default for Colourway {
+    fn default() -> Self {
+        Self {
+            name: "New colourway".to_string(),
+            replacements: BTreeMap::new(),
+            warp: vec![],
+            weft: vec![],
+        }
+    }
+}
+
+impl Colourway {
+    /// The draft's colour table with this colourway's colours in it.
+    pub fn colour_table(&self, draft: &Draft) -> BTreeMap<u32, [u8; 3]> {
+        let mut table = draft.colour_table().clone();
+        table.extend(&self.replacements);
+        table
+    }
+
+    /// Weaves colour table entry `entry` in `colour`.
+    pub fn replace(&mut self, draft: &Draft, entry: u32, colour: [u8; 3]) {
+        if draft.colour_table().get(&entry) == Some(&colour) {
+            self.replacements.remove(&entry);
+        } else {
+            self.replacements.insert(entry, colour);
+        }
+    }
+
+    /// Adds `colour` to the table as a new entry, for the sequences to use.
+    pub fn add_colour(&mut self, draft: &Draft, colour: [u8; 3]) -> u32 {
+        let entry = self
+            .colour_table(draft)
+            .keys()
+            .last()
+            .map_or(1, |last| last + 1);
+        self.replacements.insert(entry, colour);
+        entry
+    }
+
+    /// The draft in this colourway. Each thread keeps a colour table entry,
+    /// so replacements apply to the sequences too.
+    pub fn apply(&self, draft: &Draft) -> Draft {
+        draft.recoloured(
+            self.colour_table(draft),
+            |end, entry| sequence(&self.warp, end).unwrap_or(entry),
+            |pick, entry| sequence(&self.weft, pick).unwrap_or(entry),
+        )
+    }
+}
+
+fn sequence(entries: &[u32], n: u32) -> Option<u32> {
+    let i = n.checked_sub(1)? as usize;
+    (!entries.is_empty()).then(|| entries[i % entries.len()])
+}
+
+/// The colourways tried for a draft, and the one being wound and woven.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Colourways {
+    pub colourways: Vec<Colourway>,
+    /// Which of `colourways` is in use; `None` uses the draft's own colours.
+    pub active: Option<usize>,
+}
+
+impl Colourways {
+    pub fn active(&self) -> Option<&Colourway> {
+        self.active.and_then(|i| self.colourways.get(i))
+    }
+
+    /// The draft in the active colourway.
+    pub fn apply(&self, draft: &Draft) -> Draft {
+        match self.active() {
+            Some(colourway) => colourway.apply(draft),
+            None => draft.clone(),
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.colourways.remove(index);
+        self.active = match self.active {
+            Some(active) if active == index => None,
+            Some(active) if active > index => Some(active - 1),
+            active => active,
+        };
+    }
+}
+
+/// The shortest run of `entries` that repeats to give all of them.
+pub fn repeat(entries: &[u32]) -> Vec<u32> {
+    let period = (1..entries.len())
+        .find(|&p| (p..entries.len()).all(|i| entries[i] == entries[i - p]))
+        .unwrap_or(entries.len());
+    entries[..period].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draft::bit;
+
+    /// Four ends and two picks of plain weave, in entries 1 and 2.
+    fn draft() -> Draft {
+        let table = BTreeMap::from([(1, [255, 0, 0]), (2, [0, 0, 255])]);
+        Draft::for_test(2, &[bit(1), bit(2), bit(1), bit(2)], &[bit(1), bit(2)]).recoloured(
+            table,
+            |end, _| [1, 1, 2, 2][end as usize - 1],
+            |_, _| 2,
+        )
+    }
+
+    #[test]
+    fn finds_the_repeat() {
+        assert_eq!(repeat(&[1, 2, 1, 2, 1, 2]), [1, 2]);
+        assert_eq!(repeat(&[1, 1, 2, 1, 1]), [1, 1, 2]);
+        assert_eq!(repeat(&[1, 2, 3]), [1, 2, 3]);
+        assert_eq!(repeat(&[4, 4, 4]), [4]);
+        assert!(repeat(&[]).is_empty());
+    }
+
+    #[test]
+    fn replaces_by_entry() {
+        let draft = draft();
+        let mut colourway = Colourway::default();
+        colourway.replace(&draft, 2, [0, 255, 0]);
+        let woven = colourway.apply(&draft);
+        assert_eq!(woven.warp_colour(1), [255, 0, 0]);
+        assert_eq!(woven.warp_colour(3), [0, 255, 0]);
+        assert_eq!(woven.weft_colour(1), [0, 255, 0]);
+        assert_eq!(woven.warp_entry(3), 2);
+
+        // Putting the draft's colour back forgets the replacement.
+        colourway.replace(&draft, 2, [0, 0, 255]);
+        assert!(colourway.replacements.is_empty());
+    }
+
+    #[test]
+    fn repeats_sequences_from_the_table() {
+        let draft = draft();
+        let mut colourway = Colourway::default();
+        let white = colourway.add_colour(&draft, [255; 3]);
+        assert_eq!(white, 3);
+        colourway.warp = vec![white, 1];
+        let woven = colourway.apply(&draft);
+        let warp: Vec<_> = (1..=4).map(|end| woven.warp_entry(end)).collect();
+        assert_eq!(warp, [3, 1, 3, 1]);
+        assert_eq!(woven.warp_colour(1), [255; 3]);
+        assert_eq!(woven.weft_colour(2), [0, 0, 255]);
+    }
+
+    #[test]
+    fn removing_keeps_the_active_one() {
+        let mut colourways = Colourways {
+            colourways: vec![Colourway::default(); 3],
+            active: Some(2),
+        };
+        colourways.remove(0);
+        assert_eq!(colourways.active, Some(1));
+        colourways.remove(1);
+        assert_eq!(colourways.active, None);
+    }
+}
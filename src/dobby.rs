@@ -0,0 +1,440 @@
+//! Driving a computer-assisted dobby over a serial line.
+
+use std::{
+    io::{self, Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::draft::Mask;
+
+/// How long the link waits for the loom before checking it's still wanted.
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DobbyProtocol {
+    /// AVL Compu-Dobby I–III and looms like them: the shed goes as raw
+    /// bytes, eight shafts to a byte with shaft 1 in the lowest bit of the
+    /// first, and the loom sends a byte each time it wants the next pick.
+    Avl,
+    /// Seguin and other text protocols: `=C` and the shafts as a hex word,
+    /// shaft 1 lowest, ending in a carriage return. The loom reports its
+    /// state as `=s` and a hex digit, with 8 set when it wants the next pick.
+    Seguin,
+}
+
+impl DobbyProtocol {
+    pub const ALL: [DobbyProtocol; 2] = [Self::Avl, Self::Seguin];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Avl => "AVL Compu-Dobby (binary)",
+            Self::Seguin => "Seguin (=C text)",
+        }
+    }
+
+    /// What to send the loom to lift `lifted` on a loom with `shafts` shafts.
+    pub fn encode(self, lifted: Mask, shafts: u32) -> Vec<u8> {
+        match self {
+            Self::Avl => (0..shafts.div_ceil(8))
+                .map(|byte| (lifted >> (byte * 8)) as u8)
+                .collect(),
+            Self::Seguin => {
+                let width = shafts.div_ceil(4) as usize;
+                format!("=C{lifted:0width$X}\r").into_bytes()
+            }
+        }
+    }
+}
+
+/// Picks out the loom's requests for the next pick from what it sends.
+#[derive(Debug)]
+pub struct Decoder {
+    protocol: DobbyProtocol,
+    /// A text line not finished yet.
+    line: Vec<u8>,
+}
+
+impl Decoder {
+    pub fn new(protocol: DobbyProtocol) -> Self {
+        Self {
+            protocol,
+            line: vec![],
+        }
+    }
+
+    /// How many times `bytes` ask for the next pick.
+    pub fn feed(&mut self, bytes: &[u8]) -> usize {
+        match self.protocol {
+            DobbyProtocol::Avl => bytes.len(),
+            DobbyProtocol::Seguin => {
+                let mut requests = 0;
+                for &byte in bytes {
+                    if byte == b'\r' || byte == b'\n' {
+                        let line = std::mem::take(&mut self.line);
+                        if pick_wanted(&line) {
+                            requests += 1;
+                        }
+                    } else {
+                        self.line.push(byte);
+                    }
+                }
+                requests
+            }
+        }
+    }
+}
+
+/// Whether a Seguin state report asks for the next pick.
+fn pick_wanted(line: &[u8]) -> bool {
+    let Some(state) = line.strip_prefix(b"=s") else {
+        return false;
+    };
+    std::str::from_utf8(state)
+        .ok()
+        .and_then(|state| u8::from_str_radix(state.trim(), 16).ok())
+        .is_some_and(|state| state & 8 != 0)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DobbySettings {
+    pub port: String,
+    pub baud: u32,
+    pub protocol: DobbyProtocol,
+}
+
+impl Default for DobbySettings {
+    fn default() -> Self {
+        Self {
+            port: if cfg!(windows) {
+                "COM1"
+            } else {
+                "/dev/ttyUSB0"
+            }
+            .to_string(),
+            baud: 9600,
+            protocol: DobbyProtocol::Avl,
+        }
+    }
+}
+
+/// A connection to the loom.
+///
+/// Each time the loom asks for the next pick it raises the `shed_opened`
+/// flag it was opened with.
+pub struct DobbyLink {
+    writer: Box<dyn Write + Send>,
+    protocol: DobbyProtocol,
+    stop: Arc<AtomicBool>,
+    /// Why the reader stopped, if the loom went away.
+    error: Arc<RwLock<Option<String>>>,
+    reader: Option<JoinHandle<()>>,
+    /// The pick, shed and shaft count last sent, so each is only sent once.
+    sent: Option<(u32, Mask, u32)>,
+}
+
+impl DobbyLink {
+    #[cfg(feature = "dobby")]
+    pub fn open(
+        settings: &DobbySettings,
+        ctx: egui::Context,
+        shed_opened: Arc<AtomicBool>,
+    ) -> Result<Self, String> {
+        let port = serialport::new(&settings.port, settings.baud)
+            .timeout(READ_TIMEOUT)
+            .open()
+            .map_err(|e| format!("Could not open {}: {e}", settings.port))?;
+        let reader = port
+            .try_clone()
+            .map_err(|e| format!("Could not open {}: {e}", settings.port))?;
+        Ok(Self::attach(
+            reader,
+            port,
+            settings.protocol,
+            ctx,
+            shed_opened,
+        ))
+    }
+
+    /// Without the `dobby` feature we can't set the baud rate, but a
+    /// pseudo-terminal, or a port already set up with `stty`, can be opened
+    /// like any other file.
+    #[cfg(all(unix, not(feature = "dobby")))]
+    pub fn open(
+        settings: &DobbySettings,
+        ctx: egui::Context,
+        shed_opened: Arc<AtomicBool>,
+    ) -> Result<Self, String> {
+        let port = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&settings.port)
+            .map_err(|e| format!("Could not open {}: {e}", settings.port))?;
+        let reader = port
+            .try_clone()
+            .map_err(|e| format!("Could not open {}: {e}", settings.port))?;
+        Ok(Self::attach(
+            Polled(reader),
+            port,
+            settings.protocol,
+            ctx,
+            shed_opened,
+        ))
+    }
+
+    #[cfg(all(not(unix), not(feature = "dobby")))]
+    pub fn open(
+        _settings: &DobbySettings,
+        _ctx: egui::Context,
+        _shed_opened: Arc<AtomicBool>,
+    ) -> Result<Self, String> {
+        Err("Drawboy was built without serial port support".to_string())
+    }
+
+    /// Talks to a loom over an already-open reader and writer. Reads must
+    /// time out now and then, so the link can be closed.
+    pub fn attach(
+        mut reader: impl Read + Send + 'static,
+        writer: impl Write + Send + 'static,
+        protocol: DobbyProtocol,
+        ctx: egui::Context,
+        shed_opened: Arc<AtomicBool>,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let error = Arc::new(RwLock::new(None));
+        let failed = Arc::clone(&error);
+        let thread = std::thread::spawn(move || {
+            let mut decoder = Decoder::new(protocol);
+            let mut buf = [0; 64];
+            while !stopped.load(Ordering::Acquire) {
+                match reader.read(&mut buf) {
+                    Ok(0) => {
+                        *failed.write().unwrap() = Some("the port was closed".to_string());
+                        ctx.request_repaint();
+                        break;
+                    }
+                    Ok(n) => {
+                        if decoder.feed(&buf[..n]) > 0 && !stopped.load(Ordering::Acquire) {
+                            shed_opened.store(true, Ordering::Release);
+                            ctx.request_repaint();
+                        }
+                    }
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            io::ErrorKind::TimedOut
+                                | io::ErrorKind::WouldBlock
+                                | io::ErrorKind::Interrupted
+                        ) => {}
+                    Err(e) => {
+                        *failed.write().unwrap() = Some(e.to_string());
+                        ctx.request_repaint();
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            writer: Box::new(writer),
+            protocol,
+            stop,
+            error,
+            reader: Some(thread),
+            sent: None,
+        }
+    }
+
+    /// Sends the shed for `pick` on a loom with `shafts` shafts, unless the
+    /// loom already has it.
+    pub fn send(&mut self, pick: u32, lifted: Mask, shafts: u32) -> io::Result<()> {
+        if self.sent == Some((pick, lifted, shafts)) {
+            return Ok(());
+        }
+        self.writer
+            .write_all(&self.protocol.encode(lifted, shafts))?;
+        self.writer.flush()?;
+        self.sent = Some((pick, lifted, shafts));
+        Ok(())
+    }
+
+    /// Why the loom stopped being heard from, if it did.
+    pub fn error(&self) -> Option<String> {
+        self.error.read().unwrap().clone()
+    }
+}
+
+impl Drop for DobbyLink {
+    /// Waits for the reader to notice, so nothing from the loom gets through
+    /// once the link is gone.
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+/// A file that waits at most [`READ_TIMEOUT`] for something to read, like
+/// a serial port does.
+#[cfg(all(unix, not(feature = "dobby")))]
+struct Polled(std::fs::File);
+
+#[cfg(all(unix, not(feature = "dobby")))]
+impl Read for Polled {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use std::os::fd::AsRawFd;
+
+        let mut fd = libc::pollfd {
+            fd: self.0.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `fd` is a single valid pollfd that outlives the call.
+        match unsafe { libc::poll(&mut fd, 1, READ_TIMEOUT.as_millis() as i32) } {
+            -1 => Err(io::Error::last_os_error()),
+            0 => Err(io::ErrorKind::TimedOut.into()),
+            _ => self.0.read(buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draft::bit;
+
+    #[test]
+    fn encodes_sheds() {
+        let lifted = bit(1) | bit(3) | bit(10);
+        assert_eq!(DobbyProtocol::Avl.encode(lifted, 16), vec![0b101, 0b10]);
+        assert_eq!(DobbyProtocol::Seguin.encode(lifted, 16), b"=C0205\r");
+    }
+
+    #[test]
+    fn decodes_pick_requests() {
+        let mut avl = Decoder::new(DobbyProtocol::Avl);
+        assert_eq!(avl.feed(&[0x61, 0x61]), 2);
+
+        let mut seguin = Decoder::new(DobbyProtocol::Seguin);
+        assert_eq!(seguin.feed(b"=s1\r=c05\r=s"), 0);
+        // Messages can arrive in pieces.
+        assert_eq!(seguin.feed(b"8\r"), 1);
+        assert_eq!(seguin.feed(b"=sC\n=s4\r"), 1);
+    }
+
+    /// A pseudo-terminal in raw mode: the loom's end, and the path of the
+    /// end Drawboy opens. The second file keeps that end open.
+    #[cfg(unix)]
+    fn pseudo_terminal() -> (std::fs::File, String, std::fs::File) {
+        use std::{ffi::CStr, fs::File, os::fd::AsRawFd, os::fd::FromRawFd};
+
+        // SAFETY: the master fd is checked before use and handed to a File,
+        // which closes it; `name` is big enough for any pty path.
+        unsafe {
+            let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            assert!(master >= 0, "no pseudo-terminals");
+            let loom = File::from_raw_fd(master);
+            assert_eq!(libc::grantpt(master), 0);
+            assert_eq!(libc::unlockpt(master), 0);
+            let mut name = [0 as libc::c_char; 128];
+            assert_eq!(libc::ptsname_r(master, name.as_mut_ptr(), name.len()), 0);
+            let path = CStr::from_ptr(name.as_ptr()).to_str().unwrap().to_string();
+
+            let port = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&path)
+                .unwrap();
+            let mut termios = std::mem::zeroed();
+            assert_eq!(libc::tcgetattr(port.as_raw_fd(), &mut termios), 0);
+            libc::cfmakeraw(&mut termios);
+            assert_eq!(
+                libc::tcsetattr(port.as_raw_fd(), libc::TCSANOW, &termios),
+                0
+            );
+            (loom, path, port)
+        }
+    }
+
+    #[cfg(unix)]
+    fn raised_within(flag: &AtomicBool, wait: Duration) -> bool {
+        let start = std::time::Instant::now();
+        while start.elapsed() < wait {
+            if flag.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn talks_to_a_pseudo_terminal_loom() {
+        let (mut loom, path, _port) = pseudo_terminal();
+        let settings = DobbySettings {
+            port: path,
+            baud: 9600,
+            protocol: DobbyProtocol::Seguin,
+        };
+        let shed_opened = Arc::new(AtomicBool::new(false));
+        let mut link = DobbyLink::open(
+            &settings,
+            egui::Context::default(),
+            Arc::clone(&shed_opened),
+        )
+        .unwrap();
+
+        link.send(1, bit(1) | bit(3), 8).unwrap();
+        // The same shed for the same pick isn't sent twice.
+        link.send(1, bit(1) | bit(3), 8).unwrap();
+        link.send(2, bit(2) | bit(4), 8).unwrap();
+        let mut sent = [0; 10];
+        loom.read_exact(&mut sent).unwrap();
+        assert_eq!(&sent, b"=C05\r=C0A\r");
+
+        loom.write_all(b"=s1\r").unwrap();
+        assert!(!raised_within(&shed_opened, Duration::from_millis(300)));
+        loom.write_all(b"=s8\r").unwrap();
+        assert!(raised_within(&shed_opened, Duration::from_secs(2)));
+
+        // Once disconnected, the loom can't move the weaver on.
+        let start = std::time::Instant::now();
+        drop(link);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        loom.write_all(b"=s8\r").unwrap();
+        assert!(!raised_within(&shed_opened, Duration::from_millis(300)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_losing_the_loom() {
+        let (loom, path, port) = pseudo_terminal();
+        let settings = DobbySettings {
+            port: path,
+            baud: 9600,
+            protocol: DobbyProtocol::Avl,
+        };
+        let shed_opened = Arc::new(AtomicBool::new(false));
+        let link = DobbyLink::open(&settings, egui::Context::default(), shed_opened).unwrap();
+        assert_eq!(link.error(), None);
+        drop(port);
+        drop(loom);
+        let start = std::time::Instant::now();
+        while link.error().is_none() && start.elapsed() < Duration::from_secs(2) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(link.error().is_some());
+    }
+}
@@ -0,0 +1,522 @@
+use std::collections::BTreeMap;
+
+use wif::{Shaft, Units, Warp, Weft, Wif};
+
+use crate::sections::Sections;
+
+/// A set of shafts or treadles, with bit `n - 1` set for shaft/treadle `n`.
+pub type Mask = u64;
+
+/// The most shafts or treadles a [`Mask`] can describe.
+pub const MAX_SHAFTS: u32 = Mask::BITS;
+
+pub fn bit(n: u32) -> Mask {
+    1 << (n - 1)
+}
+
+/// The 1-based shaft/treadle numbers in `mask`, lowest first.
+pub fn members(mask: Mask) -> impl Iterator<Item = u32> {
+    (1..=MAX_SHAFTS).filter(move |&n| mask & bit(n) != 0)
+}
+
+/// Turns away drafts with more shafts or treadles than a [`Mask`] holds,
+/// which couldn't be woven from without losing some of them.
+pub fn check(wif: &Wif) -> Result<(), String> {
+    let threaded = wif
+        .threading
+        .iter()
+        .flat_map(|threading| threading.values());
+    let lifted = wif.liftplan.iter().flat_map(|liftplan| liftplan.values());
+    let shafts = threaded
+        .chain(lifted)
+        .flatten()
+        .map(|shaft| shaft.0)
+        .chain(wif.shafts())
+        .max()
+        .unwrap_or(0);
+    let treadles = wif
+        .treadling
+        .iter()
+        .flat_map(|treadling| treadling.values().flatten())
+        .map(|treadle| treadle.0)
+        .chain(wif.treadles())
+        .max()
+        .unwrap_or(0);
+    if shafts > MAX_SHAFTS {
+        Err(format!(
+            "This draft uses {shafts} shafts or draw units; Drawboy can only handle {MAX_SHAFTS}"
+        ))
+    } else if treadles > MAX_SHAFTS {
+        Err(format!(
+            "This draft uses {treadles} treadles; Drawboy can only handle {MAX_SHAFTS}"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// A flattened copy of the parts of a WIF that the weaving views work from.
+///
+/// Ends and picks are 1-based, like everywhere else in Drawboy; anything
+/// outside the draft reads as empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Draft {
+    pub shafts: u32,
+    pub treadles: u32,
+    /// Distance between the centres of neighbouring ends, in inches.
+    pub warp_spacing: Option<f32>,
+    /// Distance between neighbouring picks, in inches.
+    pub weft_spacing: Option<f32>,
+    /// Diameters of the warp and weft threads, in inches.
+    pub warp_thickness: Option<f32>,
+    pub weft_thickness: Option<f32>,
+    /// Spacings and thicknesses the WIF gives particular ends and picks, in
+    /// inches, in place of the ones above.
+    end_spacings: Vec<Option<f32>>,
+    end_thicknesses: Vec<Option<f32>>,
+    pick_spacings: Vec<Option<f32>>,
+    pick_thicknesses: Vec<Option<f32>>,
+    threading: Vec<Mask>,
+    /// The colour table, by entry number, with only the entries in use.
+    colour_table: BTreeMap<u32, [u8; 3]>,
+    /// The colour table entry each end and pick is in.
+    warp_colours: Vec<u32>,
+    weft_colours: Vec<u32>,
+    liftplan: Vec<Mask>,
+    treadling: Vec<Mask>,
+    /// Shafts tied to each treadle, treadle 1 first, if the WIF has a tie-up.
+    tieup: Vec<Mask>,
+    /// Whether the WIF gives each pick a row of the liftplan (or treadling
+    /// and a tie-up to work it out from) or of the treadling at all, as
+    /// opposed to an empty one.
+    lifts_given: Vec<bool>,
+    treadling_given: Vec<bool>,
+}
+
+impl Draft {
+    /// Flattens `wif`, with `sections` from the same file for the parts the
+    /// parser leaves out.
+    pub fn from_wif(wif: &Wif, sections: &Sections) -> Self {
+        let shafts = wif.shafts().unwrap_or(4).min(MAX_SHAFTS);
+        let treadles = wif.treadles().unwrap_or(6).min(MAX_SHAFTS);
+        let ends = wif.warp.as_ref().map(|warp| warp.threads).unwrap_or(0);
+        let picks = wif.weft.as_ref().map(|weft| weft.threads).unwrap_or(0);
+
+        let threading = (1..=ends)
+            .map(|end| {
+                wif.threading
+                    .as_ref()
+                    .and_then(|threading| threading.get(&Warp::from(end)))
+                    .map(|on| {
+                        (1..=shafts)
+                            .filter(|&shaft| on.contains(&Shaft::from(shaft)))
+                            .fold(0, |mask, shaft| mask | bit(shaft))
+                    })
+                    .unwrap_or(0)
+            })
+            .collect();
+        let mut colour_table = BTreeMap::new();
+        let warp_colours = colour_entries(sections, "WARP", ends, &mut colour_table, |end| {
+            wif.warp_color_u8(Warp::from(end)).unwrap_or_default()
+        });
+        let weft_colours = colour_entries(sections, "WEFT", picks, &mut colour_table, |pick| {
+            wif.weft_color_u8(Weft::from(pick)).unwrap_or_default()
+        });
+        let treadling_given: Vec<bool> = (1..=picks)
+            .map(|pick| {
+                wif.treadling
+                    .as_ref()
+                    .is_some_and(|treadling| treadling.contains_key(&Weft::from(pick)))
+            })
+            .collect();
+        let treadling: Vec<Mask> = (1..=picks)
+            .map(|pick| {
+                wif.treadling
+                    .as_ref()
+                    .and_then(|treadling| treadling.get(&Weft::from(pick)))
+                    .map(|pressed| {
+                        pressed
+                            .iter()
+                            .filter(|t| (1..=treadles).contains(&t.0))
+                            .fold(0, |mask, t| mask | bit(t.0))
+                    })
+                    .unwrap_or(0)
+            })
+            .collect();
+        // Picks the liftplan leaves out lift what their treadles are tied to.
+        let tieup = tieup(sections, shafts, treadles);
+        let lifts_given = (1..=picks)
+            .map(|pick| {
+                wif.liftplan
+                    .as_ref()
+                    .is_some_and(|liftplan| liftplan.contains_key(&Weft::from(pick)))
+                    || !tieup.is_empty() && given(&treadling_given, pick)
+            })
+            .collect();
+        let liftplan = (1..=picks)
+            .map(|pick| {
+                wif.liftplan
+                    .as_ref()
+                    .and_then(|liftplan| liftplan.get(&Weft::from(pick)))
+                    .map(|lifted| {
+                        (1..=shafts)
+                            .filter(|&shaft| lifted.contains(&Shaft::from(shaft)))
+                            .fold(0, |mask, shaft| mask | bit(shaft))
+                    })
+                    .unwrap_or_else(|| tied_lift(&tieup, lookup(&treadling, pick)))
+            })
+            .collect();
+
+        let warp_spacing = wif
+            .warp
+            .as_ref()
+            .and_then(|warp| inches(warp.spacing, warp.units));
+        let weft_spacing = wif
+            .weft
+            .as_ref()
+            .and_then(|weft| inches(weft.spacing, weft.units));
+        let warp_thickness = wif
+            .warp
+            .as_ref()
+            .and_then(|warp| inches(warp.thickness, warp.units));
+        let weft_thickness = wif
+            .weft
+            .as_ref()
+            .and_then(|weft| inches(weft.thickness, weft.units));
+        let warp_units = wif.warp.as_ref().and_then(|warp| warp.units);
+        let weft_units = wif.weft.as_ref().and_then(|weft| weft.units);
+        let end_spacings = per_thread(sections, "WARP SPACING", ends, warp_units);
+        let end_thicknesses = per_thread(sections, "WARP THICKNESS", ends, warp_units);
+        let pick_spacings = per_thread(sections, "WEFT SPACING", picks, weft_units);
+        let pick_thicknesses = per_thread(sections, "WEFT THICKNESS", picks, weft_units);
+
+        Self {
+            shafts,
+            treadles,
+            warp_spacing,
+            weft_spacing,
+            warp_thickness,
+            weft_thickness,
+            end_spacings,
+            end_thicknesses,
+            pick_spacings,
+            pick_thicknesses,
+            threading,
+            colour_table,
+            warp_colours,
+            weft_colours,
+            liftplan,
+            treadling,
+            tieup,
+            lifts_given,
+            treadling_given,
+        }
+    }
+
+    pub fn ends(&self) -> u32 {
+        self.threading.len() as u32
+    }
+
+    pub fn threading(&self, end: u32) -> Mask {
+        lookup(&self.threading, end)
+    }
+
+    pub fn warp_colour(&self, end: u32) -> [u8; 3] {
+        self.colour(self.warp_entry(end))
+    }
+
+    /// The colour table entry `end` is in, or 0 outside the draft.
+    pub fn warp_entry(&self, end: u32) -> u32 {
+        entry(&self.warp_colours, end)
+    }
+
+    /// The colour of a colour table entry.
+    pub fn colour(&self, entry: u32) -> [u8; 3] {
+        self.colour_table.get(&entry).copied().unwrap_or_default()
+    }
+
+    pub fn colour_table(&self) -> &BTreeMap<u32, [u8; 3]> {
+        &self.colour_table
+    }
+
+    /// The colour table entries used in the draft, warp first, each once in
+    /// the order they first appear.
+    pub fn colours_used(&self) -> Vec<u32> {
+        distinct(self.warp_colours.iter().chain(&self.weft_colours))
+    }
+
+    /// The colour table entries the weft uses, in the order they first appear.
+    pub fn weft_colours_used(&self) -> Vec<u32> {
+        distinct(&self.weft_colours)
+    }
+
+    /// A copy of the draft with `colour_table` in place of its own, and each
+    /// end's and pick's entry passed through `warp` and `weft`, which get the
+    /// thread's number and current entry.
+    pub fn recoloured(
+        &self,
+        colour_table: BTreeMap<u32, [u8; 3]>,
+        warp: impl Fn(u32, u32) -> u32,
+        weft: impl Fn(u32, u32) -> u32,
+    ) -> Self {
+        let recolour = |entries: &[u32], f: &dyn Fn(u32, u32) -> u32| {
+            (1..).zip(entries).map(|(n, &entry)| f(n, entry)).collect()
+        };
+        Self {
+            colour_table,
+            warp_colours: recolour(&self.warp_colours, &warp),
+            weft_colours: recolour(&self.weft_colours, &weft),
+            ..self.clone()
+        }
+    }
+
+    /// The distance from the centre of `end` to the next, in inches.
+    pub fn end_spacing(&self, end: u32) -> Option<f32> {
+        measured(&self.end_spacings, end).or(self.warp_spacing)
+    }
+
+    pub fn end_thickness(&self, end: u32) -> Option<f32> {
+        measured(&self.end_thicknesses, end).or(self.warp_thickness)
+    }
+
+    /// The distance from `pick` to the next, in inches.
+    pub fn pick_spacing(&self, pick: u32) -> Option<f32> {
+        measured(&self.pick_spacings, pick).or(self.weft_spacing)
+    }
+
+    pub fn pick_thickness(&self, pick: u32) -> Option<f32> {
+        measured(&self.pick_thicknesses, pick).or(self.weft_thickness)
+    }
+
+    pub fn picks(&self) -> u32 {
+        self.liftplan.len() as u32
+    }
+
+    pub fn weft_colour(&self, pick: u32) -> [u8; 3] {
+        self.colour(self.weft_entry(pick))
+    }
+
+    /// The colour table entry `pick` is in, or 0 outside the draft.
+    pub fn weft_entry(&self, pick: u32) -> u32 {
+        entry(&self.weft_colours, pick)
+    }
+
+    pub fn lift(&self, pick: u32) -> Mask {
+        lookup(&self.liftplan, pick)
+    }
+
+    pub fn treadling(&self, pick: u32) -> Mask {
+        lookup(&self.treadling, pick)
+    }
+
+    pub fn has_lift(&self, pick: u32) -> bool {
+        given(&self.lifts_given, pick)
+    }
+
+    pub fn has_treadling(&self, pick: u32) -> bool {
+        given(&self.treadling_given, pick)
+    }
+
+    /// Whether any pick's lift is known, from a liftplan or a tie-up and
+    /// treadling. Without one, every pick reads as lifting nothing.
+    pub fn has_liftplan(&self) -> bool {
+        self.lifts_given.contains(&true)
+    }
+
+    pub fn liftplan(&self) -> &[Mask] {
+        &self.liftplan
+    }
+
+    pub fn tieup(&self) -> &[Mask] {
+        &self.tieup
+    }
+}
+
+#[cfg(test)]
+impl Draft {
+    /// A draft threaded and lifted as given, one mask per end and per pick.
+    pub fn for_test(shafts: u32, threading: &[Mask], liftplan: &[Mask]) -> Self {
+        Self {
+            shafts,
+            treadles: shafts,
+            threading: threading.to_vec(),
+            colour_table: BTreeMap::from([(1, [0; 3])]),
+            warp_colours: vec![1; threading.len()],
+            weft_colours: vec![1; liftplan.len()],
+            liftplan: liftplan.to_vec(),
+            treadling: liftplan.to_vec(),
+            lifts_given: vec![true; liftplan.len()],
+            treadling_given: vec![true; liftplan.len()],
+            ..Self::default()
+        }
+    }
+
+    /// The same draft woven from `treadling` through `tieup` too.
+    pub fn with_treadling(mut self, tieup: &[Mask], treadling: &[Mask]) -> Self {
+        self.tieup = tieup.to_vec();
+        self.treadling = treadling.to_vec();
+        self
+    }
+}
+
+/// Converts a WIF measurement to inches. WIF measurements are in decipoints
+/// unless the section says otherwise.
+fn inches(value: Option<f64>, units: Option<Units>) -> Option<f32> {
+    let value = value.filter(|&value| value > 0.)? as f32;
+    Some(match units {
+        Some(Units::Inches) => value,
+        Some(Units::Centimeters) => value / 2.54,
+        Some(Units::Decipoints) | None => value / 720.,
+    })
+}
+
+/// Measurements for each of `threads` threads from a section such as
+/// `[WARP SPACING]`, in inches.
+fn per_thread(
+    sections: &Sections,
+    section: &str,
+    threads: u32,
+    units: Option<Units>,
+) -> Vec<Option<f32>> {
+    let values = sections.numbered(section);
+    (1..=threads)
+        .map(|n| inches(values.get(&n).and_then(|value| value.parse().ok()), units))
+        .collect()
+}
+
+/// The colour table entry of each of `threads` threads, from the thread's
+/// own entry in `[WARP COLORS]` or `[WEFT COLORS]`, else the section's
+/// `Color`. Threads the WIF gives no entry share one per colour, numbered
+/// after the table's. Each entry's colour goes into `table` as `colour` gives
+/// it for the first thread in it.
+fn colour_entries(
+    sections: &Sections,
+    section: &str,
+    threads: u32,
+    table: &mut BTreeMap<u32, [u8; 3]>,
+    colour: impl Fn(u32) -> [u8; 3],
+) -> Vec<u32> {
+    let parse = |value: &str| value.split(',').next()?.trim().parse::<u32>().ok();
+    let given = sections.numbered(&format!("{section} COLORS"));
+    let default = sections.value(section, "COLOR").and_then(parse);
+    let listed = sections.numbered("COLOR TABLE");
+    (1..=threads)
+        .map(|n| {
+            let colour = colour(n);
+            let entry = given
+                .get(&n)
+                .and_then(|value| parse(value))
+                .or(default)
+                .or_else(|| {
+                    table
+                        .iter()
+                        .find(|(_, &other)| other == colour)
+                        .map(|(&entry, _)| entry)
+                })
+                .unwrap_or_else(|| {
+                    let last = table.keys().chain(listed.keys()).max();
+                    last.map_or(1, |last| last + 1)
+                });
+            table.entry(entry).or_insert(colour);
+            entry
+        })
+        .collect()
+}
+
+/// The shafts each treadle is tied to, treadle 1 first, from the WIF's
+/// `[TIEUP]` section. Empty if there isn't one.
+fn tieup(sections: &Sections, shafts: u32, treadles: u32) -> Vec<Mask> {
+    let tied = sections.numbered("TIEUP");
+    if tied.is_empty() {
+        return vec![];
+    }
+    (1..=treadles)
+        .map(|treadle| {
+            tied.get(&treadle)
+                .into_iter()
+                .flat_map(|value| value.split(','))
+                .filter_map(|shaft| shaft.trim().parse().ok())
+                .filter(|shaft| (1..=shafts).contains(shaft))
+                .fold(0, |mask, shaft| mask | bit(shaft))
+        })
+        .collect()
+}
+
+/// The shafts lifted by pressing `treadles` together.
+pub fn tied_lift(tieup: &[Mask], treadles: Mask) -> Mask {
+    members(treadles)
+        .map(|treadle| lookup(tieup, treadle))
+        .fold(0, |lift, shafts| lift | shafts)
+}
+
+fn distinct<'a>(entries: impl IntoIterator<Item = &'a u32>) -> Vec<u32> {
+    let mut distinct = vec![];
+    for &entry in entries {
+        if !distinct.contains(&entry) {
+            distinct.push(entry);
+        }
+    }
+    distinct
+}
+
+fn entry(entries: &[u32], n: u32) -> u32 {
+    n.checked_sub(1)
+        .and_then(|i| entries.get(i as usize))
+        .copied()
+        .unwrap_or(0)
+}
+
+fn measured(values: &[Option<f32>], n: u32) -> Option<f32> {
+    n.checked_sub(1)
+        .and_then(|i| values.get(i as usize))
+        .copied()
+        .flatten()
+}
+
+fn lookup(masks: &[Mask], n: u32) -> Mask {
+    n.checked_sub(1)
+        .and_then(|i| masks.get(i as usize))
+        .copied()
+        .unwrap_or(0)
+}
+
+fn given(given: &[bool], n: u32) -> bool {
+    n.checked_sub(1)
+        .and_then(|i| given.get(i as usize))
+        .copied()
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lifts_by_the_tieup() {
+        let sections = Sections::parse("[TIEUP]\n1=1,2\n2=2,3,9\n4=4\n");
+        let tied = tieup(&sections, 4, 4);
+        assert_eq!(tied, [bit(1) | bit(2), bit(2) | bit(3), 0, bit(4)]);
+        assert_eq!(tied_lift(&tied, bit(1)), bit(1) | bit(2));
+        assert_eq!(tied_lift(&tied, bit(1) | bit(4)), bit(1) | bit(2) | bit(4));
+        assert_eq!(tied_lift(&tied, 0), 0);
+        assert!(tieup(&Sections::parse(""), 4, 4).is_empty());
+    }
+
+    #[test]
+    fn reads_colour_entries() {
+        let sections = Sections::parse(
+            "[COLOR TABLE]\n1=255,0,0\n2=0,0,255\n[WARP]\nColor=2\n[WARP COLORS]\n3=1\n",
+        );
+        let mut table = BTreeMap::new();
+        let warp = colour_entries(&sections, "WARP", 3, &mut table, |end| {
+            [[0, 0, 255], [0, 0, 255], [255, 0, 0]][end as usize - 1]
+        });
+        assert_eq!(warp, [2, 2, 1]);
+        // No entries for the weft, so it gets its own past the table's.
+        let weft = colour_entries(&sections, "WEFT", 3, &mut table, |pick| {
+            [[0, 0, 255], [9, 9, 9], [9, 9, 9]][pick as usize - 1]
+        });
+        assert_eq!(weft, [2, 3, 3]);
+        assert_eq!(table[&3], [9, 9, 9]);
+    }
+}
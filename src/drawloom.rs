@@ -0,0 +1,164 @@
+//! Splitting a drawloom draft into pattern pulls and ground picks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::draft::{tied_lift, Draft, Mask};
+
+/// What the pattern half of a drawloom is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DrawUnits {
+    /// Pattern shafts, each carrying a block of ends.
+    Shafts,
+    /// Single-unit draw cords, each carrying one unit of ends.
+    Cords,
+}
+
+impl DrawUnits {
+    pub fn name(self) -> &'static str {
+        match self {
+            DrawUnits::Shafts => "shaft",
+            DrawUnits::Cords => "cord",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawStep {
+    /// Change what's drawn before starting a new pattern row.
+    Pull {
+        pattern_row: u32,
+        /// The first pick woven with this pull.
+        pick: u32,
+        pull: Mask,
+        release: Mask,
+        drawn: Mask,
+    },
+    /// Weave one pick with the ground shafts.
+    Ground {
+        pattern_row: u32,
+        pick: u32,
+        /// The treadles to press, if the draft treadles its ground.
+        treadles: Option<Mask>,
+        lifted: Mask,
+    },
+}
+
+impl DrawStep {
+    pub fn pattern_row(&self) -> u32 {
+        match *self {
+            DrawStep::Pull { pattern_row, .. } | DrawStep::Ground { pattern_row, .. } => {
+                pattern_row
+            }
+        }
+    }
+
+    pub fn pick(&self) -> u32 {
+        match *self {
+            DrawStep::Pull { pick, .. } | DrawStep::Ground { pick, .. } => pick,
+        }
+    }
+}
+
+/// The step that starts weaving `pick`: its pull, if it begins a pattern
+/// row, or else its ground pick. Steps count from 1.
+pub fn step_for_pick(steps: &[DrawStep], pick: u32) -> u32 {
+    steps
+        .iter()
+        .position(|step| step.pick() == pick)
+        .map_or(1, |i| i as u32 + 1)
+}
+
+/// Shafts 1 to `n`.
+fn first(n: u32) -> Mask {
+    Mask::MAX.checked_shr(Mask::BITS - n).unwrap_or(0)
+}
+
+/// Interleaves the pattern pulls with the ground treadling.
+///
+/// A pattern row is a run of picks that all have the same pattern shafts
+/// drawn. Each one starts with a pull, followed by its ground picks.
+pub fn sequence(draft: &Draft, ground_shafts: u32) -> Vec<DrawStep> {
+    // Shafts above the ground shafts are the pattern shafts (or cords).
+    let ground = first(ground_shafts.min(draft.shafts));
+    let pattern = first(draft.shafts) & !ground;
+    let mut steps = vec![];
+    let mut drawn = 0;
+    let mut pattern_row = 0;
+    for (pick, &lifted) in (1..).zip(draft.liftplan()) {
+        if pattern_row == 0 || lifted & pattern != drawn {
+            pattern_row += 1;
+            let now_drawn = lifted & pattern;
+            steps.push(DrawStep::Pull {
+                pattern_row,
+                pick,
+                pull: now_drawn & !drawn,
+                release: drawn & !now_drawn,
+                drawn: now_drawn,
+            });
+            drawn = now_drawn;
+        }
+        // The ground comes from the treadling where the draft has one to
+        // read through its tie-up, and from the liftplan otherwise.
+        let treadles =
+            (draft.has_treadling(pick) && !draft.tieup().is_empty()).then(|| draft.treadling(pick));
+        let ground_lift = treadles.map_or(lifted, |treadles| tied_lift(draft.tieup(), treadles));
+        steps.push(DrawStep::Ground {
+            pattern_row,
+            pick,
+            treadles,
+            lifted: ground_lift & ground,
+        });
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::draft::bit;
+
+    use super::*;
+
+    #[test]
+    fn ground_from_the_treadling() {
+        // Shafts 1 and 2 are ground, 3 and 4 pattern. The liftplan lifts
+        // the wrong ground shafts, which the treadling puts right.
+        let liftplan = [bit(1) | bit(3), bit(1) | bit(3), bit(2) | bit(4)];
+        let draft = Draft::for_test(4, &[bit(1), bit(3)], &liftplan)
+            .with_treadling(&[bit(1), bit(2)], &[bit(2), bit(1), bit(1)]);
+        let grounds: Vec<_> = sequence(&draft, 2)
+            .into_iter()
+            .filter_map(|step| match step {
+                DrawStep::Ground {
+                    treadles, lifted, ..
+                } => Some((treadles, lifted)),
+                DrawStep::Pull { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            grounds,
+            [
+                (Some(bit(2)), bit(2)),
+                (Some(bit(1)), bit(1)),
+                (Some(bit(1)), bit(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn ground_from_the_liftplan() {
+        let liftplan = [bit(1) | bit(3), bit(2) | bit(3)];
+        let draft = Draft::for_test(4, &[bit(1), bit(3)], &liftplan);
+        let steps = sequence(&draft, 2);
+        assert_eq!(steps.len(), 3);
+        assert!(matches!(
+            steps[2],
+            DrawStep::Ground {
+                pick: 2,
+                treadles: None,
+                lifted,
+                ..
+            } if lifted == bit(2)
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+//! Measuring how far threads float over the cloth without interlacing.
+
+use crate::draft::Draft;
+
+/// The length of the float each cell of the drawdown is part of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Floats {
+    pub ends: u32,
+    pub picks: u32,
+    /// For each cell, pick by pick, how many picks the warp floats over
+    /// there, or 0 if the weft is on top.
+    warp: Vec<u32>,
+    /// For each cell, how many ends the weft floats over there, or 0 if the
+    /// warp is on top.
+    weft: Vec<u32>,
+}
+
+/// The longest float of one kind, and where it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Longest {
+    pub length: u32,
+    pub end: u32,
+    pub pick: u32,
+}
+
+impl Floats {
+    pub fn new(draft: &Draft) -> Self {
+        let (ends, picks) = (draft.ends(), draft.picks());
+        let up = |end: u32, pick: u32| draft.lift(pick) & draft.threading(end) != 0;
+
+        let mut warp = vec![0; (ends * picks) as usize];
+        for end in 1..=ends {
+            // The treadling repeats, so warp floats carry on from the last
+            // pick round to the first.
+            let column: Vec<bool> = (1..=picks).map(|pick| up(end, pick)).collect();
+            for (pick, length) in (1..).zip(run_lengths(&column, true)) {
+                warp[index(ends, end, pick)] = length;
+            }
+        }
+        let mut weft = vec![0; (ends * picks) as usize];
+        for pick in 1..=picks {
+            let row: Vec<bool> = (1..=ends).map(|end| !up(end, pick)).collect();
+            for (end, length) in (1..).zip(run_lengths(&row, false)) {
+                weft[index(ends, end, pick)] = length;
+            }
+        }
+        Self {
+            ends,
+            picks,
+            warp,
+            weft,
+        }
+    }
+
+    pub fn warp(&self, end: u32, pick: u32) -> u32 {
+        self.warp[index(self.ends, end, pick)]
+    }
+
+    pub fn weft(&self, end: u32, pick: u32) -> u32 {
+        self.weft[index(self.ends, end, pick)]
+    }
+
+    pub fn longest_warp(&self) -> Option<Longest> {
+        longest(&self.warp, self.ends)
+    }
+
+    pub fn longest_weft(&self) -> Option<Longest> {
+        longest(&self.weft, self.ends)
+    }
+
+    /// How many cells of the drawdown are part of a float longer than `limit`.
+    pub fn cells_over(&self, limit: u32) -> usize {
+        self.warp
+            .iter()
+            .zip(&self.weft)
+            .filter(|&(&warp, &weft)| warp.max(weft) > limit)
+            .count()
+    }
+}
+
+fn index(ends: u32, end: u32, pick: u32) -> usize {
+    ((pick - 1) * ends + end - 1) as usize
+}
+
+fn longest(lengths: &[u32], ends: u32) -> Option<Longest> {
+    let (i, &length) = lengths
+        .iter()
+        .enumerate()
+        .max_by_key(|&(i, &length)| (length, std::cmp::Reverse(i)))?;
+    Some(Longest {
+        length,
+        end: i as u32 % ends + 1,
+        pick: i as u32 / ends + 1,
+    })
+}
+
+/// For each cell, the length of the run of `true` cells it's part of, or 0.
+/// With `wrap`, a run can carry on from the end round to the start.
+fn run_lengths(cells: &[bool], wrap: bool) -> Vec<u32> {
+    let n = cells.len();
+    if wrap && cells.iter().all(|&cell| cell) {
+        return vec![n as u32; n];
+    }
+    // Start just after a gap, so no run is split by the wrap.
+    let start = if wrap {
+        cells.iter().position(|&cell| !cell).unwrap_or(0)
+    } else {
+        0
+    };
+    let mut lengths = vec![0; n];
+    let mut i = 0;
+    while i < n {
+        if !cells[(start + i) % n] {
+            i += 1;
+            continue;
+        }
+        let run = (i..n).take_while(|&j| cells[(start + j) % n]).count();
+        for j in i..i + run {
+            lengths[(start + j) % n] = run as u32;
+        }
+        i += run;
+    }
+    lengths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draft::bit;
+
+    #[test]
+    fn runs_wrap_round() {
+        assert_eq!(run_lengths(&[true, false, true, true], false), [1, 0, 2, 2]);
+        assert_eq!(run_lengths(&[true, false, true, true], true), [3, 0, 3, 3]);
+        assert_eq!(run_lengths(&[true; 3], true), [3; 3]);
+        assert_eq!(run_lengths(&[false; 3], true), [0; 3]);
+        assert!(run_lengths(&[], true).is_empty());
+    }
+
+    #[test]
+    fn plain_weave_has_no_floats() {
+        let draft = Draft::for_test(2, &[bit(1), bit(2)], &[bit(1), bit(2)]);
+        let floats = Floats::new(&draft);
+        assert_eq!(floats.longest_warp().map(|longest| longest.length), Some(1));
+        assert_eq!(floats.longest_weft().map(|longest| longest.length), Some(1));
+        assert_eq!(floats.cells_over(1), 0);
+    }
+
+    #[test]
+    fn finds_the_longest_float() {
+        // End 1 is up for the first three of four picks, and the weft
+        // floats over ends 2 to 4 on the last.
+        let threading = [bit(1), bit(2), bit(2), bit(2)];
+        let liftplan = [bit(1) | bit(2), bit(1), bit(1), bit(2)];
+        let floats = Floats::new(&Draft::for_test(2, &threading, &liftplan));
+        assert_eq!(floats.warp(1, 2), 3);
+        assert_eq!(floats.weft(1, 2), 0);
+        assert_eq!(
+            floats.longest_warp(),
+            Some(Longest {
+                length: 3,
+                end: 1,
+                pick: 1
+            })
+        );
+        assert_eq!(floats.weft(3, 2), 3);
+        assert_eq!(
+            floats
+                .longest_weft()
+                .map(|longest| (longest.end, longest.pick)),
+            Some((2, 2))
+        );
+        assert_eq!(floats.cells_over(2), 3 + 6);
+
+        let empty = Floats::new(&Draft::for_test(2, &[], &[]));
+        assert_eq!(empty.longest_warp(), None);
+    }
+}
@@ -0,0 +1,56 @@
+//! Working out how many heddles each shaft needs, and where.
+
+use crate::draft::{bit, Draft};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShaftHeddles {
+    pub shaft: u32,
+    /// Heddles needed between the left edge of the warp and the centre.
+    pub left: u32,
+    /// Heddles needed between the centre and the right edge of the warp.
+    pub right: u32,
+    /// Heddles on the shaft that the warp doesn't use, to push out of the way.
+    pub surplus: u32,
+    /// Heddles the warp needs that the shaft doesn't have.
+    pub shortfall: u32,
+}
+
+impl ShaftHeddles {
+    pub fn needed(&self) -> u32 {
+        self.left + self.right
+    }
+}
+
+/// Counts the heddles each shaft needs on either side of the centre of the warp,
+/// given `heddles_per_shaft` heddles on each shaft of the loom.
+///
+/// Shafts are listed in `shafts` order. With an odd number of ends, the middle
+/// one counts as being on the side away from end 1.
+pub fn plan(
+    draft: &Draft,
+    heddles_per_shaft: u32,
+    shafts: &[u32],
+    end_one_on_left: bool,
+) -> Vec<ShaftHeddles> {
+    let ends = draft.ends();
+    shafts
+        .iter()
+        .map(|&shaft| {
+            let on_shaft = |end: &u32| draft.threading(*end) & bit(shaft) != 0;
+            let near = (1..=ends / 2).filter(on_shaft).count() as u32;
+            let far = (ends / 2 + 1..=ends).filter(on_shaft).count() as u32;
+            let (left, right) = if end_one_on_left {
+                (near, far)
+            } else {
+                (far, near)
+            };
+            ShaftHeddles {
+                shaft,
+                left,
+                right,
+                surplus: heddles_per_shaft.saturating_sub(left + right),
+                shortfall: (left + right).saturating_sub(heddles_per_shaft),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,93 @@
+//! Working out how much cloth has been woven.
+
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+/// The picks per inch a cloth could plausibly be woven at.
+pub const PPI_RANGE: RangeInclusive<f32> = 1.0..=200.0;
+
+/// A ruler measurement of the cloth woven so far.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// Picks woven when it was measured.
+    pub picks: u32,
+    /// Length woven, in inches.
+    pub length: f32,
+}
+
+/// The picks per inch that best fits the measurements, if there are any.
+pub fn refined_ppi(measurements: &[Measurement]) -> Option<f32> {
+    // Least squares for a line through the origin: the cloth starts at no
+    // length with no picks.
+    let (picks_by_length, length_squared) = measurements.iter().fold((0., 0.), |(pl, ll), m| {
+        (pl + m.picks as f32 * m.length, ll + m.length * m.length)
+    });
+    (length_squared > 0.)
+        .then(|| (picks_by_length / length_squared).clamp(*PPI_RANGE.start(), *PPI_RANGE.end()))
+}
+
+/// Checks `new` could be a true measurement, given those already taken: it
+/// must be at a plausible picks per inch, and cloth only gets longer.
+pub fn check(measurements: &[Measurement], new: &Measurement) -> Result<(), String> {
+    if new.picks == 0 || new.length <= 0. {
+        return Err("Weave some cloth before measuring it.".to_string());
+    }
+    let ppi = new.picks as f32 / new.length;
+    if !PPI_RANGE.contains(&ppi) {
+        return Err(format!(
+            "That's {ppi:.1} picks per inch, which can't be right."
+        ));
+    }
+    let out_of_order = measurements.iter().find(|m| {
+        (m.picks < new.picks && m.length > new.length)
+            || (m.picks > new.picks && m.length < new.length)
+    });
+    match out_of_order {
+        Some(m) => Err(format!(
+            "The cloth measured {:.1}\" after {} picks.",
+            m.length, m.picks
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Inches woven after `picks` picks at `ppi`, counting on from the most
+/// recent measurement.
+pub fn woven_length(measurements: &[Measurement], picks: u32, ppi: f32) -> f32 {
+    let (from_picks, from_length) = measurements
+        .iter()
+        .filter(|m| m.picks <= picks)
+        .max_by_key(|m| m.picks)
+        .map_or((0, 0.), |m| (m.picks, m.length));
+    from_length + (picks - from_picks) as f32 / ppi.max(0.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(picks: u32, length: f32) -> Measurement {
+        Measurement { picks, length }
+    }
+
+    #[test]
+    fn refines_ppi() {
+        assert_eq!(refined_ppi(&[]), None);
+        assert_eq!(refined_ppi(&[at(100, 5.), at(200, 10.)]), Some(20.));
+        // However far off the measurements are, the result stays plausible.
+        assert_eq!(refined_ppi(&[at(100_000, 1.)]), Some(200.));
+    }
+
+    #[test]
+    fn refuses_implausible_measurements() {
+        let taken = [at(100, 5.)];
+        assert!(check(&taken, &at(200, 10.)).is_ok());
+        assert!(check(&taken, &at(0, 1.)).is_err());
+        assert!(check(&taken, &at(200, 0.)).is_err());
+        assert!(check(&taken, &at(200, 0.5)).is_err());
+        // Shorter than it was at fewer picks.
+        assert!(check(&taken, &at(200, 4.)).is_err());
+        assert!(check(&taken, &at(50, 6.)).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drawloom::DrawUnits;
+
+/// What the physical loom can do, as opposed to what the draft asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoomProfile {
+    pub treadles: u32,
+    pub heddles_per_shaft: u32,
+    pub dents_per_inch: u32,
+    /// Usable width of the reed, in inches.
+    pub reed_width: f32,
+    /// Width of each section of a sectional beam, in inches.
+    pub section_width: f32,
+    /// How much warp one turn of the beam winds on, in inches.
+    pub beam_circumference: f32,
+    /// On a drawloom, how many of the shafts are ground shafts. The rest are
+    /// pattern shafts or draw cords.
+    pub ground_shafts: u32,
+    pub draw_units: DrawUnits,
+    pub orientation: Orientation,
+}
+
+impl Default for LoomProfile {
+    fn default() -> Self {
+        Self {
+            treadles: 10,
+            heddles_per_shaft: 200,
+            dents_per_inch: 12,
+            reed_width: 36.,
+            section_width: 2.,
+            beam_circumference: 36.,
+            ground_shafts: 4,
+            draw_units: DrawUnits::Shafts,
+            orientation: Orientation::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShaftOrder {
+    /// Shaft 1 is the front shaft, nearest the weaver.
+    OneAtFront,
+    /// Shaft 1 is the back shaft.
+    OneAtBack,
+}
+
+/// How the draft maps onto the loom as the weaver sees it.
+///
+/// Views always draw the front shaft first: at the top, or on the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Orientation {
+    /// The side of the loom end 1 is on.
+    pub end_one: Side,
+    pub shafts: ShaftOrder,
+    /// The side of the loom threading starts from.
+    pub start: Side,
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self {
+            end_one: Side::Left,
+            shafts: ShaftOrder::OneAtFront,
+            start: Side::Left,
+        }
+    }
+}
+
+impl Orientation {
+    /// Shaft numbers from the front of the loom to the back.
+    pub fn shaft_order(&self, shafts: u32) -> Vec<u32> {
+        match self.shafts {
+            ShaftOrder::OneAtFront => (1..=shafts).collect(),
+            ShaftOrder::OneAtBack => (1..=shafts).rev().collect(),
+        }
+    }
+
+    /// Whether threading starts at end 1 and works up, rather than starting
+    /// at the last end.
+    pub fn threads_ascending(&self) -> bool {
+        self.end_one == self.start
+    }
+
+    /// The end threaded at `step`, counting from 1 at the starting side.
+    ///
+    /// Steps outside the warp give ends outside the warp, so callers can lay
+    /// out placeholders.
+    pub fn end_at_step(&self, step: i32, ends: u32) -> i32 {
+        if self.threads_ascending() {
+            step
+        } else {
+            ends as i32 + 1 - step
+        }
+    }
+
+    /// The inverse of [`Orientation::end_at_step`].
+    pub fn step_at_end(&self, end: u32, ends: u32) -> i32 {
+        self.end_at_step(end as i32, ends)
+    }
+}
@@ -1,10 +1,35 @@
 use app::MyApp;
+use sections::Sections;
 use wif::parse;
 
 use eframe::egui;
 
 mod app;
+mod calculator;
+mod cloth;
+mod colourway;
+mod dobby;
+mod draft;
+mod drawloom;
 mod ewma;
+mod floats;
+mod heddles;
+mod length;
+mod loom;
+mod motif;
+mod notes;
+mod reminder;
+mod sectional;
+mod sections;
+mod selvedge;
+mod shuttle;
+mod sleying;
+mod structure;
+mod tieup;
+mod unweave;
+mod verify;
+mod weft_runs;
+mod yarn;
 
 fn main() -> eframe::Result {
     env_logger::init();
@@ -28,7 +53,7 @@ fn main() -> eframe::Result {
             // This gives us image support:
             // egui_extras::install_image_loaders(&cc.egui_ctx);
 
-            Ok(Box::new(MyApp::new(wif, cc)))
+            Ok(Box::new(MyApp::new(wif, Sections::parse(w), cc)))
         }),
     )
 }
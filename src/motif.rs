@@ -0,0 +1,221 @@
+//! Compressing a threading into repeated motifs, e.g. "1-2-3-4 ×12, then 4-3-2-1 ×2".
+
+use std::{fmt, ops::Range};
+
+use crate::draft::{members, Mask};
+
+/// The longest motif we look for repeats of.
+const MAX_MOTIF: usize = 32;
+
+/// A run of the threading: a motif of ends, threaded `repeats` times in a row.
+///
+/// Positions are threading steps (1-based, in the order the ends are
+/// threaded), not end numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Motif {
+    pub first_step: u32,
+    pub ends: Vec<Mask>,
+    pub repeats: u32,
+}
+
+impl Motif {
+    pub fn len(&self) -> u32 {
+        self.ends.len() as u32 * self.repeats
+    }
+
+    pub fn contains(&self, step: u32) -> bool {
+        (self.first_step..self.first_step + self.len()).contains(&step)
+    }
+
+    /// Which repeat `step` falls in, and where in that repeat, both from 0.
+    pub fn position(&self, step: u32) -> (u32, u32) {
+        let offset = step - self.first_step;
+        let motif_len = self.ends.len() as u32;
+        (offset / motif_len, offset % motif_len)
+    }
+}
+
+impl fmt::Display for Motif {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ends: Vec<_> = self.ends.iter().map(|&end| shafts_label(end)).collect();
+        write!(f, "{}", ends.join("-"))?;
+        if self.repeats > 1 {
+            write!(f, " ×{}", self.repeats)?;
+        }
+        Ok(())
+    }
+}
+
+/// The shafts an end is threaded on, e.g. "3", or "1+3" for an end on two shafts.
+pub fn shafts_label(end: Mask) -> String {
+    if end == 0 {
+        return "·".to_string();
+    }
+    members(end)
+        .map(|shaft| shaft.to_string())
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Splits a threading, given in threading order, into motifs.
+///
+/// At each point we take whichever motif repeats over the most ends. Where
+/// nothing repeats, straight and point runs (each end on a shaft next to the
+/// last) are kept together, and anything else is gathered up as it comes.
+pub fn motifs(threading: &[Mask]) -> Vec<Motif> {
+    let mut motifs = vec![];
+    let mut loose: Vec<Mask> = vec![];
+    let mut i = 0;
+
+    while i < threading.len() {
+        let rest = &threading[i..];
+        let (len, repeats) = (1..=MAX_MOTIF.min(rest.len()))
+            .map(|len| (len, repeats(rest, len)))
+            .filter(|&(_, repeats)| repeats >= 2)
+            .fold((1, 1), |best, candidate| {
+                if candidate.0 * candidate.1 > best.0 * best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+        if repeats >= 2 {
+            flush(&mut loose, &mut motifs, i);
+            motifs.push(Motif {
+                first_step: i as u32 + 1,
+                ends: rest[..len].to_vec(),
+                repeats: repeats as u32,
+            });
+            i += len * repeats;
+            continue;
+        }
+
+        let run = run_length(rest);
+        if run >= 3 {
+            flush(&mut loose, &mut motifs, i);
+            motifs.push(Motif {
+                first_step: i as u32 + 1,
+                ends: rest[..run].to_vec(),
+                repeats: 1,
+            });
+            i += run;
+        } else {
+            loose.push(rest[0]);
+            i += 1;
+        }
+    }
+    flush(&mut loose, &mut motifs, i);
+    motifs
+}
+
+/// Gathers up the loose ends before step `i + 1` as a motif of their own.
+fn flush(loose: &mut Vec<Mask>, motifs: &mut Vec<Motif>, i: usize) {
+    if !loose.is_empty() {
+        motifs.push(Motif {
+            first_step: (i - loose.len()) as u32 + 1,
+            ends: std::mem::take(loose),
+            repeats: 1,
+        });
+    }
+}
+
+/// How many times the first `len` ends repeat back to back.
+fn repeats(ends: &[Mask], len: usize) -> usize {
+    let motif = &ends[..len];
+    ends.chunks_exact(len)
+        .take_while(|&chunk| chunk == motif)
+        .count()
+}
+
+/// How many ends in a row each sit on a single shaft next to the previous one's.
+fn run_length(ends: &[Mask]) -> usize {
+    let single = |end: Mask| end.count_ones() == 1;
+    if ends.is_empty() || !single(ends[0]) {
+        return 0;
+    }
+    1 + ends
+        .windows(2)
+        .take_while(|pair| single(pair[1]) && (pair[0] == pair[1] << 1 || pair[1] == pair[0] << 1))
+        .count()
+}
+
+/// The index of the motif containing `step`.
+pub fn find(motifs: &[Motif], step: u32) -> Option<usize> {
+    motifs.iter().position(|motif| motif.contains(step))
+}
+
+/// The first step of the repeat after the one containing `step`.
+pub fn next_repeat(motifs: &[Motif], step: u32) -> Option<u32> {
+    let motif = &motifs[find(motifs, step)?];
+    let (repeat, _) = motif.position(step);
+    Some(motif.first_step + (repeat + 1) * motif.ends.len() as u32)
+}
+
+/// The first step of the repeat containing `step`, or of the one before if
+/// we're already at its start.
+pub fn previous_repeat(motifs: &[Motif], step: u32) -> Option<u32> {
+    let index = find(motifs, step)?;
+    let motif = &motifs[index];
+    let (repeat, within) = motif.position(step);
+    let motif_len = motif.ends.len() as u32;
+    if within > 0 || repeat > 0 {
+        let repeat = if within > 0 { repeat } else { repeat - 1 };
+        return Some(motif.first_step + repeat * motif_len);
+    }
+    let previous = motifs.get(index.checked_sub(1)?)?;
+    Some(previous.first_step + previous.len() - previous.ends.len() as u32)
+}
+
+/// Splits the threading into batches of at most `max` ends that follow its
+/// natural groups, returned as ranges of steps.
+///
+/// A batch never spans a change of block or of warp colour, and otherwise
+/// holds as many whole repeats as will fit. Only a repeat longer than `max`
+/// gets split.
+pub fn batches(motifs: &[Motif], colours: &[[u8; 3]], max: u32) -> Vec<Range<u32>> {
+    let max = max.max(1);
+    let Some(end) = motifs.last().map(|motif| motif.first_step + motif.len()) else {
+        return vec![];
+    };
+    let colour_changes = (2..end).filter(|&step| {
+        let i = step as usize - 1;
+        colours.get(i) != colours.get(i - 1)
+    });
+    let mut hard: Vec<u32> = motifs
+        .iter()
+        .map(|motif| motif.first_step)
+        .chain(colour_changes)
+        .chain([end])
+        .collect();
+    hard.sort_unstable();
+    hard.dedup();
+    let soft: Vec<u32> = motifs
+        .iter()
+        .flat_map(|motif| {
+            (1..motif.repeats).map(|repeat| motif.first_step + repeat * motif.ends.len() as u32)
+        })
+        .collect();
+
+    let mut batches = vec![];
+    let mut start = 1;
+    while start < end {
+        let limit = start + max;
+        let next_hard = hard
+            .iter()
+            .copied()
+            .find(|&step| step > start)
+            .unwrap_or(end);
+        let stop = if next_hard <= limit {
+            next_hard
+        } else {
+            soft.iter()
+                .copied()
+                .filter(|&step| step > start && step <= limit)
+                .max()
+                .unwrap_or(limit)
+        };
+        batches.push(start..stop);
+        start = stop;
+    }
+    batches
+}
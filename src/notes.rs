@@ -0,0 +1,150 @@
+//! Notes and bookmarks on rows and threads of a draft.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    Row(u32),
+    Thread(u32),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Row(row) => write!(f, "Row {row}"),
+            Target::Thread(end) => write!(f, "Thread {end}"),
+        }
+    }
+}
+
+/// A note on a row or thread. A note with no text is just a bookmark.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    pub target: Target,
+    pub text: String,
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.text.is_empty() {
+            write!(f, "🔖 {}", self.target)
+        } else {
+            write!(f, "{}: {}", self.target, self.text)
+        }
+    }
+}
+
+/// The text of a WIF file with `notes` added to its `[NOTES]` section.
+/// Notes already in the file are kept, and ours aren't repeated if they've
+/// been written before.
+///
+/// WIF values end at a `;`, so notes containing one are refused rather than
+/// cut short.
+pub fn with_notes(contents: &str, notes: &[Note]) -> Result<String, String> {
+    if let Some(note) = notes.iter().find(|note| note.text.contains(';')) {
+        return Err(format!(
+            "{} has a ';', which WIF files can't hold",
+            note.target
+        ));
+    }
+
+    let mut out = String::new();
+    let mut section = String::new();
+    let mut listed = false;
+    let mut entries: Vec<String> = vec![];
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            if section == "CONTENTS" && !listed {
+                out.push_str("NOTES=true\n");
+            }
+            section = trimmed[1..trimmed.len() - 1].to_ascii_uppercase();
+        } else if section == "CONTENTS"
+            && trimmed
+                .split('=')
+                .next()
+                .is_some_and(|key| key.trim().eq_ignore_ascii_case("NOTES"))
+        {
+            out.push_str("NOTES=true\n");
+            listed = true;
+            continue;
+        }
+        if section != "NOTES" {
+            out.push_str(line);
+            out.push('\n');
+        } else if let Some((_, value)) = trimmed.split_once('=') {
+            if !trimmed.starts_with(';') {
+                entries.push(value.to_string());
+            }
+        }
+    }
+    if section == "CONTENTS" && !listed {
+        out.push_str("NOTES=true\n");
+    }
+
+    for note in notes {
+        let text = if note.text.is_empty() {
+            "bookmark"
+        } else {
+            &note.text
+        };
+        let entry = format!("{}: {text}", note.target);
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+    // Trailing blank lines would pile up each time the notes are written.
+    let mut out = out.trim_end().to_string();
+    out.push_str("\n\n[NOTES]\n");
+    for (i, entry) in (1..).zip(entries) {
+        out.push_str(&format!("{i}={entry}\n"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIF: &str = "[WIF]\nVersion=1.1\n[CONTENTS]\nWEAVING=true\nNOTES=true\n\
+                       [NOTES]\n; from the designer\n1=Use a floating selvedge\n\
+                       [WEAVING]\nShafts=4\n";
+
+    fn note(target: Target, text: &str) -> Note {
+        Note {
+            target,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn keeps_existing_notes() {
+        let notes = [
+            note(Target::Row(3), "switch bobbin"),
+            note(Target::Thread(7), ""),
+        ];
+        let written = with_notes(WIF, &notes).unwrap();
+        assert!(written.contains("[WEAVING]\nShafts=4\n"));
+        assert_eq!(written.matches("NOTES=true").count(), 1);
+        assert!(written.ends_with(
+            "[NOTES]\n1=Use a floating selvedge\n2=Row 3: switch bobbin\n3=Thread 7: bookmark\n"
+        ));
+        // Writing again doesn't repeat them.
+        assert_eq!(with_notes(&written, &notes).unwrap(), written);
+    }
+
+    #[test]
+    fn lists_notes_in_contents() {
+        let written = with_notes("[CONTENTS]\nWEAVING=true\n", &[]).unwrap();
+        assert_eq!(written, "[CONTENTS]\nWEAVING=true\nNOTES=true\n\n[NOTES]\n");
+    }
+
+    #[test]
+    fn refuses_semicolons() {
+        let notes = [note(Target::Row(1), "hemstitch; tightly")];
+        assert!(with_notes(WIF, &notes).is_err());
+    }
+}
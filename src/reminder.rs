@@ -0,0 +1,174 @@
+//! Reminders that come up as the weaving reaches certain points.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trigger {
+    /// On reaching this row, in every repeat.
+    Row(u32),
+    /// Every so many picks of the piece.
+    EveryRows(u32),
+    /// On starting this repeat of the treadling.
+    Repeat(u32),
+    EveryInches(f32),
+    EveryMinutes(f32),
+    /// On reaching the planned length of cloth.
+    Finished,
+}
+
+impl Trigger {
+    /// One of each kind of trigger, with a typical setting.
+    pub const KINDS: [Trigger; 6] = [
+        Trigger::Row(1),
+        Trigger::EveryRows(20),
+        Trigger::Repeat(2),
+        Trigger::EveryInches(4.),
+        Trigger::EveryMinutes(30.),
+        Trigger::Finished,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Trigger::Row(_) => "At row",
+            Trigger::EveryRows(_) => "Every n rows",
+            Trigger::Repeat(_) => "At repeat",
+            Trigger::EveryInches(_) => "Every n inches",
+            Trigger::EveryMinutes(_) => "Every n minutes",
+            Trigger::Finished => "At the end",
+        }
+    }
+
+    /// Whether moving on from `before` to `after` reaches this trigger. With
+    /// no `before`, the weaving is just starting at `after`.
+    pub fn fires(&self, before: Option<&Progress>, after: &Progress) -> bool {
+        // Whether `after` has passed another multiple of `every` than `before`.
+        let crossed = |before: f32, after: f32, every: f32| {
+            every > 0. && (after / every).floor() > (before / every).floor()
+        };
+        let Some(before) = before else {
+            return match *self {
+                Trigger::Row(row) => after.row == row,
+                Trigger::Repeat(repeat) => after.repeat == repeat,
+                _ => false,
+            };
+        };
+        match *self {
+            Trigger::Row(row) => after.row == row,
+            Trigger::EveryRows(rows) => {
+                crossed(before.picks as f32, after.picks as f32, rows as f32)
+            }
+            Trigger::Repeat(repeat) => after.repeat == repeat && before.repeat != repeat,
+            Trigger::EveryInches(inches) => crossed(before.woven, after.woven, inches),
+            Trigger::EveryMinutes(minutes) => crossed(before.elapsed, after.elapsed, minutes * 60.),
+            Trigger::Finished => before.woven < after.planned && after.woven >= after.planned,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub message: String,
+    pub trigger: Trigger,
+}
+
+impl Default for Reminder {
+    fn default() -> Self {
+        Self {
+            message: "Advance the warp".to_string(),
+            trigger: Trigger::EveryInches(4.),
+        }
+    }
+}
+
+/// Where the weaving has got to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub row: u32,
+    pub repeat: u32,
+    pub picks: u32,
+    /// Inches woven, and planned.
+    pub woven: f32,
+    pub planned: f32,
+    /// Seconds spent weaving, going by the row timer.
+    pub elapsed: f32,
+}
+
+/// The messages of the reminders due on moving from `before` to `after`,
+/// or on starting at `after`.
+pub fn due(reminders: &[Reminder], before: Option<&Progress>, after: &Progress) -> Vec<String> {
+    reminders
+        .iter()
+        .filter(|reminder| reminder.trigger.fires(before, after))
+        .map(|reminder| reminder.message.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(row: u32, repeat: u32, picks: u32) -> Progress {
+        Progress {
+            row,
+            repeat,
+            picks,
+            woven: picks as f32 / 20.,
+            planned: 10.,
+            elapsed: picks as f32 * 3.,
+        }
+    }
+
+    #[test]
+    fn row_fires_on_entering_it() {
+        let trigger = Trigger::Row(1);
+        assert!(trigger.fires(None, &at(1, 1, 0)));
+        assert!(!trigger.fires(Some(&at(1, 1, 0)), &at(2, 1, 1)));
+        // Every repeat comes back round to row 1.
+        assert!(trigger.fires(Some(&at(8, 1, 7)), &at(1, 2, 8)));
+    }
+
+    #[test]
+    fn repeat_fires_once() {
+        let trigger = Trigger::Repeat(2);
+        assert!(trigger.fires(Some(&at(8, 1, 7)), &at(1, 2, 8)));
+        assert!(!trigger.fires(Some(&at(1, 2, 8)), &at(2, 2, 9)));
+        assert!(!trigger.fires(None, &at(1, 1, 0)));
+    }
+
+    #[test]
+    fn every_so_often() {
+        let rows = Trigger::EveryRows(20);
+        assert!(rows.fires(Some(&at(19, 1, 19)), &at(20, 1, 20)));
+        assert!(!rows.fires(Some(&at(20, 1, 20)), &at(21, 1, 21)));
+        assert!(!rows.fires(None, &at(1, 1, 0)));
+
+        let inches = Trigger::EveryInches(1.);
+        assert!(inches.fires(Some(&at(19, 1, 19)), &at(20, 1, 20)));
+        assert!(!inches.fires(Some(&at(20, 1, 20)), &at(21, 1, 21)));
+
+        let minutes = Trigger::EveryMinutes(1.);
+        assert!(minutes.fires(Some(&at(19, 1, 19)), &at(20, 1, 20)));
+        assert!(!minutes.fires(Some(&at(20, 1, 20)), &at(21, 1, 21)));
+    }
+
+    #[test]
+    fn finished() {
+        let trigger = Trigger::Finished;
+        assert!(trigger.fires(Some(&at(199, 1, 199)), &at(200, 1, 200)));
+        assert!(!trigger.fires(Some(&at(200, 1, 200)), &at(201, 1, 201)));
+    }
+
+    #[test]
+    fn due_messages() {
+        let reminders = [
+            Reminder {
+                message: "Hemstitch".to_string(),
+                trigger: Trigger::Row(1),
+            },
+            Reminder::default(),
+        ];
+        assert_eq!(due(&reminders, None, &at(1, 1, 0)), ["Hemstitch"]);
+        assert!(due(&reminders, Some(&at(1, 1, 0)), &at(2, 1, 1)).is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+//! Planning a warp wound section by section onto a sectional beam.
+
+use std::ops::Range;
+
+use crate::draft::Draft;
+
+/// One section of the beam, wound from a spool rack in one go.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// The ends wound in this section.
+    pub ends: Range<u32>,
+    /// The colour of each spool, in the order they go through the tension box.
+    pub spools: Vec<[u8; 3]>,
+}
+
+impl Section {
+    /// How many spools differ from `previous`'s, counting any added or taken away.
+    pub fn changes_from(&self, previous: &Section) -> usize {
+        let differing = self
+            .spools
+            .iter()
+            .zip(&previous.spools)
+            .filter(|(a, b)| a != b)
+            .count();
+        differing + self.spools.len().abs_diff(previous.spools.len())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionalPlan {
+    pub sections: Vec<Section>,
+    pub ends_per_section: u32,
+    /// Turns of the beam to wind each section's length.
+    pub revolutions: f32,
+}
+
+/// Splits the warp into sections `section_width` inches wide at `epi` ends per
+/// inch, each wound `warp_length` inches long on a beam that takes up
+/// `circumference` inches a turn.
+pub fn plan(
+    draft: &Draft,
+    epi: f32,
+    section_width: f32,
+    warp_length: f32,
+    circumference: f32,
+) -> SectionalPlan {
+    let ends_per_section = ((epi * section_width).round() as u32).max(1);
+    let sections = (1..=draft.ends())
+        .step_by(ends_per_section as usize)
+        .map(|first| {
+            let ends = first..(first + ends_per_section).min(draft.ends() + 1);
+            Section {
+                spools: ends.clone().map(|end| draft.warp_colour(end)).collect(),
+                ends,
+            }
+        })
+        .collect();
+    SectionalPlan {
+        sections,
+        ends_per_section,
+        revolutions: warp_length / circumference.max(1.),
+    }
+}
+
+/// Runs of the same colour, as (colour, count).
+pub fn runs(spools: &[[u8; 3]]) -> Vec<([u8; 3], u32)> {
+    let mut runs: Vec<([u8; 3], u32)> = vec![];
+    for &colour in spools {
+        match runs.last_mut() {
+            Some((last, count)) if *last == colour => *count += 1,
+            _ => runs.push((colour, 1)),
+        }
+    }
+    runs
+}
@@ -0,0 +1,68 @@
+//! Reading the parts of a WIF file that the parser doesn't give us.
+
+use std::collections::BTreeMap;
+
+/// The keys and values in each section of a WIF file. Section names and
+/// keys are kept in upper case, as WIF doesn't care about case, and
+/// comments after a `;` are dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sections(BTreeMap<String, BTreeMap<String, String>>);
+
+impl Sections {
+    pub fn parse(contents: &str) -> Self {
+        let mut sections: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        let mut section = None;
+        for line in contents.lines() {
+            let line = line.split(';').next().unwrap_or_default().trim();
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                let name = name.trim().to_ascii_uppercase();
+                section = Some(sections.entry(name).or_default());
+            } else if let (Some(section), Some((key, value))) = (&mut section, line.split_once('='))
+            {
+                section.insert(key.trim().to_ascii_uppercase(), value.trim().to_string());
+            }
+        }
+        Self(sections)
+    }
+
+    pub fn value(&self, section: &str, key: &str) -> Option<&str> {
+        self.0
+            .get(section)
+            .and_then(|section| section.get(key))
+            .map(String::as_str)
+    }
+
+    /// The values in a section keyed by thread number, such as
+    /// `[WARP SPACING]`, leaving out any that aren't numbered.
+    pub fn numbered(&self, section: &str) -> BTreeMap<u32, &str> {
+        self.0
+            .get(section)
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| Some((key.parse().ok()?, value.as_str())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_sections() {
+        let sections = Sections::parse(
+            "[WIF]\nVersion=1.1\n; a comment\n[warp spacing]\n1=0.1\n2 = 0.2 ; thick\n\
+             Junk\n[COLOR TABLE]\n1=255,0,0\n",
+        );
+        assert_eq!(sections.value("WIF", "VERSION"), Some("1.1"));
+        assert_eq!(sections.numbered("WIF").len(), 0);
+        let spacing = sections.numbered("WARP SPACING");
+        assert_eq!(spacing.get(&1), Some(&"0.1"));
+        assert_eq!(spacing.get(&2), Some(&"0.2"));
+        assert_eq!(sections.numbered("COLOR TABLE").get(&1), Some(&"255,0,0"));
+        assert!(sections.numbered("WEFT SPACING").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+//! Checking whether the weft catches the edge ends.
+
+use crate::{
+    draft::Draft,
+    loom::Side,
+    shuttle::{side_name, Throw},
+};
+
+/// A pick whose weft doesn't wrap around the edge end on the side it
+/// starts from, so the loop from the last pick pulls out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Miss {
+    pub pick: u32,
+    pub side: Side,
+}
+
+/// The picks of the draft, thrown as in `throws`, that miss an edge end.
+///
+/// A pick catches the edge end on the side it starts from if that end has
+/// changed places since the same shuttle's last pick, which ended there.
+/// `left` and `right` are the edge ends on each side.
+pub fn misses(draft: &Draft, throws: &[Throw], left: u32, right: u32) -> Vec<Miss> {
+    let edge_up = |pick: u32, side: Side| {
+        let end = match side {
+            Side::Left => left,
+            Side::Right => right,
+        };
+        draft.lift(pick) & draft.threading(end) != 0
+    };
+    let mut last_pick: Vec<Option<u32>> = vec![];
+    let mut misses = vec![];
+    for (pick, throw) in (1..).zip(throws) {
+        let shuttle = throw.shuttle as usize;
+        if last_pick.len() <= shuttle {
+            last_pick.resize(shuttle + 1, None);
+        }
+        if let Some(previous) = last_pick[shuttle] {
+            if edge_up(previous, throw.from) == edge_up(pick, throw.from) {
+                misses.push(Miss {
+                    pick,
+                    side: throw.from,
+                });
+            }
+        }
+        last_pick[shuttle] = Some(pick);
+    }
+    misses
+}
+
+/// Which way round a floating selvedge to take a pick starting from `from`:
+/// over it going in, under it coming out.
+pub fn floating_hint(from: Side) -> String {
+    let to = match from {
+        Side::Left => Side::Right,
+        Side::Right => Side::Left,
+    };
+    format!(
+        "Over the {} floating selvedge, under the {}",
+        side_name(from),
+        side_name(to)
+    )
+}
@@ -0,0 +1,143 @@
+//! Keeping track of several shuttles, one per weft colour.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::loom::Side;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShuttleSettings {
+    /// The side the first pick is thrown from. Picks alternate from there.
+    pub first_side: Side,
+    /// Shuttles chosen for weft colours, by colour table entry, numbered
+    /// from 0.
+    pub assignments: BTreeMap<u32, u32>,
+}
+
+impl Default for ShuttleSettings {
+    fn default() -> Self {
+        Self {
+            first_side: Side::Left,
+            assignments: BTreeMap::new(),
+        }
+    }
+}
+
+impl ShuttleSettings {
+    /// The shuttle carrying colour table entry `entry`, where `weft` is the
+    /// entries the weft uses in the order they first appear. Colours that
+    /// haven't been given a shuttle have one of their own, numbered by where
+    /// they come in `weft`.
+    pub fn shuttle(&self, weft: &[u32], entry: u32) -> u32 {
+        self.assignments.get(&entry).copied().unwrap_or_else(|| {
+            weft.iter()
+                .position(|&used| used == entry)
+                .unwrap_or_default() as u32
+        })
+    }
+
+    /// How many shuttles the colours in `weft` are carried by.
+    pub fn shuttles(&self, weft: &[u32]) -> u32 {
+        let mut shuttles: Vec<u32> = weft
+            .iter()
+            .map(|&entry| self.shuttle(weft, entry))
+            .collect();
+        shuttles.sort_unstable();
+        shuttles.dedup();
+        shuttles.len() as u32
+    }
+}
+
+/// "A" for shuttle 0, "B" for shuttle 1, and so on.
+pub fn letter(shuttle: u32) -> char {
+    char::from_u32('A' as u32 + shuttle % 26).unwrap_or('?')
+}
+
+pub fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Left => "left",
+        Side::Right => "right",
+    }
+}
+
+fn other(side: Side) -> Side {
+    match side {
+        Side::Left => Side::Right,
+        Side::Right => Side::Left,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throw {
+    pub shuttle: u32,
+    /// The side the shuttle sits on, ready to be thrown.
+    pub from: Side,
+    /// The side the pick ought to start from, alternating pick by pick.
+    pub expected: Side,
+}
+
+impl Throw {
+    pub fn wrong_side(&self) -> bool {
+        self.from != self.expected
+    }
+}
+
+/// Follows each shuttle back and forth across the warp, for picks carried
+/// by `shuttles` in turn. A shuttle starts on whichever side its first pick
+/// should come from.
+pub fn throws(shuttles: impl Iterator<Item = u32>, first_side: Side) -> Vec<Throw> {
+    let mut sides: Vec<Option<Side>> = vec![];
+    let mut expected = first_side;
+    let mut throws = vec![];
+    for shuttle in shuttles {
+        let i = shuttle as usize;
+        if sides.len() <= i {
+            sides.resize(i + 1, None);
+        }
+        let from = sides[i].unwrap_or(expected);
+        sides[i] = Some(other(from));
+        throws.push(Throw {
+            shuttle,
+            from,
+            expected,
+        });
+        expected = other(expected);
+    }
+    throws
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_shuttles_from_the_weft() {
+        let mut settings = ShuttleSettings::default();
+        let weft = [3, 1];
+        assert_eq!(settings.shuttle(&weft, 3), 0);
+        assert_eq!(settings.shuttle(&weft, 1), 1);
+        assert_eq!(settings.shuttles(&weft), 2);
+
+        // Colours the weft no longer uses don't count.
+        settings.assignments.insert(7, 4);
+        assert_eq!(settings.shuttles(&[3]), 1);
+
+        settings.assignments.insert(1, 0);
+        assert_eq!(settings.shuttles(&weft), 1);
+    }
+
+    #[test]
+    fn follows_each_shuttle() {
+        let thrown = throws([0, 1, 1, 0].into_iter(), Side::Left);
+        let from: Vec<_> = thrown.iter().map(|throw| throw.from).collect();
+        assert_eq!(from, [Side::Left, Side::Right, Side::Left, Side::Right]);
+        assert!(thrown.iter().all(|throw| !throw.wrong_side()));
+
+        // Shuttle A is still on the right when its next pick should start
+        // from the left.
+        let thrown = throws([0, 1, 0].into_iter(), Side::Left);
+        assert!(thrown[2].wrong_side());
+    }
+}
@@ -0,0 +1,91 @@
+//! Working out how the warp goes through the reed.
+
+use std::ops::Range;
+
+/// How the ends are spread through the reed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DentingPlan {
+    /// The steps (in threading order) sleyed through each dent, including
+    /// empty ranges for skipped dents.
+    pub dents: Vec<Range<u32>>,
+    /// The dent of the reed, counting from the side we start on, that the
+    /// first end goes through to centre the warp.
+    pub first_dent: u32,
+    pub width: f32,
+    /// Ends left over once every dent of the reed is used.
+    pub unsleyed: u32,
+}
+
+impl DentingPlan {
+    /// Ends in each dent, for one repeat of the denting pattern.
+    pub fn pattern(&self) -> Vec<u32> {
+        let counts: Vec<u32> = self.dents.iter().map(|dent| dent.len() as u32).collect();
+        // The last dent takes whatever is left over, so leave it out.
+        let body = &counts[..counts.len().saturating_sub(1)];
+        if body.is_empty() {
+            return counts;
+        }
+        let period = (1..=body.len())
+            .find(|&period| body.iter().zip(&body[period..]).all(|(a, b)| a == b))
+            .unwrap_or(body.len());
+        body[..period].to_vec()
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.dents.iter().filter(|dent| dent.is_empty()).count()
+    }
+}
+
+/// Spreads `ends` ends at `epi` ends per inch through a reed with
+/// `dents_per_inch` dents per inch and `reed_width` inches wide.
+///
+/// Dents hold as even a number of ends as the sett allows, so 15 e.p.i. in a
+/// 10-dent reed is sleyed 2, 1, 2, 1, and 5 e.p.i. in the same reed skips
+/// every other dent. A warp too wide for the reed fills it, and the ends
+/// left over are counted as unsleyed.
+pub fn plan(ends: u32, epi: f32, dents_per_inch: u32, reed_width: f32) -> DentingPlan {
+    let per_dent = (epi / dents_per_inch.max(1) as f32) as f64;
+    let reed_dents = (reed_width * dents_per_inch as f32) as u32;
+    let mut dents = vec![];
+    let mut placed = 0;
+    if per_dent > 0. {
+        while placed < ends && dents.len() < reed_dents as usize {
+            let upto = ((dents.len() + 1) as f64 * per_dent).round() as u32;
+            let upto = upto.min(ends);
+            dents.push(placed + 1..upto + 1);
+            placed = upto;
+        }
+    }
+    let used = dents.len() as u32;
+    DentingPlan {
+        first_dent: reed_dents.saturating_sub(used) / 2 + 1,
+        width: used as f32 / dents_per_inch.max(1) as f32,
+        dents,
+        unsleyed: ends - placed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spreads_the_ends() {
+        let denting = plan(30, 15., 10, 10.);
+        assert_eq!(denting.pattern(), [2, 1]);
+        assert_eq!(denting.dents.len(), 20);
+        assert_eq!(denting.first_dent, 41);
+        assert_eq!(denting.unsleyed, 0);
+    }
+
+    #[test]
+    fn fills_a_reed_too_narrow() {
+        // A tiny sett would otherwise need millions of dents.
+        let denting = plan(1000, 0.001, 10, 2.);
+        assert_eq!(denting.dents.len(), 20);
+        assert_eq!(denting.unsleyed, 1000);
+        let denting = plan(100, 20., 10, 2.);
+        assert_eq!(denting.dents.len(), 20);
+        assert_eq!(denting.unsleyed, 60);
+    }
+}
@@ -0,0 +1,297 @@
+//! Recognising the weave structure of a draft.
+
+use std::collections::HashSet;
+
+use crate::draft::{bit, Draft, Mask};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub structure: String,
+    /// Ends and picks in the smallest repeat of the drawdown.
+    pub repeat: (u32, u32),
+    /// The fraction of the cloth's face that's warp.
+    pub warp_face: f32,
+    /// Different combinations of shafts lifted.
+    pub sheds: usize,
+    /// Ends per inch over picks per inch, if the draft gives both.
+    pub balance: Option<f32>,
+}
+
+impl Analysis {
+    pub fn facing(&self) -> &'static str {
+        if self.warp_face > 0.6 {
+            "warp-faced"
+        } else if self.warp_face < 0.4 {
+            "weft-faced"
+        } else {
+            "even-faced"
+        }
+    }
+}
+
+/// Works out what kind of cloth `draft` makes. `end_one_on_left` decides
+/// which way a twill's diagonal runs.
+pub fn analyse(draft: &Draft, end_one_on_left: bool) -> Analysis {
+    let (ends, picks) = (draft.ends() as usize, draft.picks() as usize);
+    let sheds = draft.liftplan().iter().collect::<HashSet<_>>().len();
+    let balance = draft
+        .warp_spacing
+        .zip(draft.weft_spacing)
+        .map(|(warp, weft)| weft / warp);
+    if ends == 0 || picks == 0 {
+        return Analysis {
+            structure: "Empty draft".to_string(),
+            repeat: (ends as u32, picks as u32),
+            warp_face: 0.,
+            sheds,
+            balance,
+        };
+    }
+
+    // Columns of the drawdown, one per end, true where the warp is up.
+    let columns: Vec<Vec<bool>> = (1..=draft.ends())
+        .map(|end| {
+            (1..=draft.picks())
+                .map(|pick| draft.lift(pick) & draft.threading(end) != 0)
+                .collect()
+        })
+        .collect();
+    let rows: Vec<Vec<bool>> = (0..picks)
+        .map(|pick| columns.iter().map(|column| column[pick]).collect())
+        .collect();
+
+    let width = period(&columns);
+    let height = period(&rows);
+    let unit: Vec<Vec<bool>> = rows[..height]
+        .iter()
+        .map(|row| row[..width].to_vec())
+        .collect();
+
+    let up = columns.iter().flatten().filter(|&&up| up).count();
+    let warp_face = up as f32 / (ends * picks) as f32;
+
+    Analysis {
+        structure: classify(&unit, draft, end_one_on_left),
+        repeat: (width as u32, height as u32),
+        warp_face,
+        sheds,
+        balance,
+    }
+}
+
+fn classify(unit: &[Vec<bool>], draft: &Draft, end_one_on_left: bool) -> String {
+    let (height, width) = (unit.len(), unit[0].len());
+    let cell = |row: usize, column: usize| unit[row % height][column % width];
+
+    if width == 2 && height == 2 && cell(0, 0) != cell(0, 1) && cell(0, 0) != cell(1, 0) {
+        return "Plain weave".to_string();
+    }
+    for block in 2..=3 {
+        let checked = |flip: bool| {
+            (0..height).all(|r| {
+                (0..width).all(|c| cell(r, c) == (((r / block + c / block) % 2 == 0) != flip))
+            })
+        };
+        if width == 2 * block && height == 2 * block && (checked(false) || checked(true)) {
+            return format!("{block}/{block} basket weave");
+        }
+    }
+    if width == 2 && height > 2 && (0..height).all(|r| cell(r, 0) != cell(r, 1)) {
+        return "Warp rib".to_string();
+    }
+    if height == 2 && width > 2 && (0..width).all(|c| cell(0, c) != cell(1, c)) {
+        return "Weft rib".to_string();
+    }
+
+    if width == height && width >= 3 {
+        let n = width;
+        // How far each pick's interlacing moves along from the last.
+        let shift =
+            (1..n).find(|&s| (0..n).all(|r| (0..n).all(|c| cell(r + 1, c + s) == cell(r, c))));
+        if let Some(shift) = shift {
+            let ups = unit[0].iter().filter(|&&up| up).count();
+            if (ups == 1 || ups == n - 1)
+                && n >= 5
+                && (2..=n - 2).contains(&shift)
+                && gcd(shift, n) == 1
+            {
+                return format!("{n}-end satin");
+            }
+            if shift == 1 || shift == n - 1 {
+                // Moving towards higher ends, with end 1 on the left, rises to the right.
+                let rises_right = (shift == 1) == end_one_on_left;
+                let direction = if rises_right { "Z" } else { "S" };
+                return format!("{} {direction} twill", ratio(&unit[0]));
+            }
+        }
+    }
+
+    let threading: Vec<Mask> = (1..=draft.ends()).map(|end| draft.threading(end)).collect();
+    if summer_and_winter(&threading) {
+        return "Summer & winter".to_string();
+    }
+    if twill_threading(&threading, draft.shafts) && twill_lifts(draft.liftplan(), draft.shafts) {
+        let lifted: Vec<bool> = (1..=draft.shafts)
+            .map(|shaft| draft.lift(1) & bit(shaft) != 0)
+            .collect();
+        return format!("{} point or broken twill", ratio(&lifted));
+    }
+    if overshot(&threading, draft.shafts) {
+        return "Overshot".to_string();
+    }
+    "Unrecognised structure".to_string()
+}
+
+/// The smallest number of lines after which `lines` repeat.
+fn period(lines: &[Vec<bool>]) -> usize {
+    (1..lines.len())
+        .find(|&p| (p..lines.len()).all(|i| lines[i] == lines[i - p]))
+        .unwrap_or(lines.len())
+        .max(1)
+}
+
+/// The interlacing of a row of a repeat as alternating runs of warp and
+/// weft, e.g. "2/2" or "3/1", starting with the warp.
+fn ratio(row: &[bool]) -> String {
+    let n = row.len();
+    let Some(start) = (0..n).find(|&i| row[i] && !row[(i + n - 1) % n]) else {
+        return if row.first() == Some(&true) {
+            format!("{n}/0")
+        } else {
+            format!("0/{n}")
+        };
+    };
+    let mut runs: Vec<usize> = vec![];
+    let mut last = None;
+    for i in 0..n {
+        let up = row[(start + i) % n];
+        if last == Some(up) {
+            *runs.last_mut().unwrap() += 1;
+        } else {
+            runs.push(1);
+        }
+        last = Some(up);
+    }
+    runs.iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Every other end on the two tie-down shafts, 1 and 2 in turn, with the
+/// pattern ends between on the others.
+fn summer_and_winter(threading: &[Mask]) -> bool {
+    if threading.len() < 8 {
+        return false;
+    }
+    (0..2).any(|phase| {
+        (0..threading.len()).all(|i| {
+            let end = threading[i];
+            if i % 2 == phase {
+                let tie_down = if (i / 2) % 2 == 0 { bit(1) } else { bit(2) };
+                end == tie_down
+            } else {
+                end != 0 && end & (bit(1) | bit(2)) == 0
+            }
+        })
+    })
+}
+
+/// Ends alternate between odd and even shafts, so tabby can be woven
+/// between the pattern picks, and a pair of shafts repeats to make blocks.
+fn overshot(threading: &[Mask], shafts: u32) -> bool {
+    let odd: Mask = (1..=shafts)
+        .step_by(2)
+        .fold(0, |mask, shaft| mask | bit(shaft));
+    let single = threading.iter().all(|end| end.count_ones() == 1);
+    let alternating = threading
+        .windows(2)
+        .all(|pair| (pair[0] & odd != 0) != (pair[1] & odd != 0));
+    let blocks = threading
+        .windows(4)
+        .any(|ends| ends[0] == ends[2] && ends[1] == ends[3]);
+    shafts >= 4 && threading.len() >= 8 && single && alternating && blocks
+}
+
+/// Each end on a shaft next to the last one's, as in straight and point
+/// draws, counting the last shaft as next to the first.
+fn twill_threading(threading: &[Mask], shafts: u32) -> bool {
+    let next = |a: Mask, b: Mask| b == a << 1 || (a == bit(shafts) && b == bit(1));
+    threading.len() >= 3
+        && threading.windows(2).all(|pair| {
+            pair[0].count_ones() == 1 && (next(pair[0], pair[1]) || next(pair[1], pair[0]))
+        })
+}
+
+/// Each pick lifts the shafts of the one before, moved along by one shaft
+/// either way, as in a twill treadled as drawn in.
+fn twill_lifts(liftplan: &[Mask], shafts: u32) -> bool {
+    if shafts < 3 || liftplan.len() < 2 {
+        return false;
+    }
+    let all = Mask::MAX >> (Mask::BITS - shafts);
+    let rotate_up = |mask: Mask| ((mask << 1) | (mask >> (shafts - 1))) & all;
+    let rotate_down = |mask: Mask| ((mask >> 1) | (mask << (shafts - 1))) & all;
+    liftplan
+        .windows(2)
+        .all(|pair| pair[1] == rotate_up(pair[0]) || pair[1] == rotate_down(pair[0]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A straight draw on `shafts` shafts, `ends` ends wide, woven with
+    /// `lifts` in turn for `picks` picks.
+    fn draft(shafts: u32, ends: u32, lifts: &[Mask], picks: usize) -> Draft {
+        let threading: Vec<Mask> = (0..ends).map(|end| bit(end % shafts + 1)).collect();
+        let liftplan: Vec<Mask> = lifts.iter().copied().cycle().take(picks).collect();
+        Draft::for_test(shafts, &threading, &liftplan)
+    }
+
+    #[test]
+    fn empty_draft() {
+        let analysis = analyse(&Draft::for_test(4, &[], &[]), true);
+        assert_eq!(analysis.structure, "Empty draft");
+        let analysis = analyse(&Draft::for_test(4, &[bit(1), bit(2)], &[]), true);
+        assert_eq!(analysis.structure, "Empty draft");
+    }
+
+    #[test]
+    fn plain_weave() {
+        let analysis = analyse(&draft(2, 8, &[bit(1), bit(2)], 8), true);
+        assert_eq!(analysis.structure, "Plain weave");
+        assert_eq!(analysis.repeat, (2, 2));
+        assert_eq!(analysis.facing(), "even-faced");
+    }
+
+    #[test]
+    fn twill_direction() {
+        let lifts = [
+            bit(1) | bit(2),
+            bit(2) | bit(3),
+            bit(3) | bit(4),
+            bit(4) | bit(1),
+        ];
+        let analysis = analyse(&draft(4, 16, &lifts, 16), true);
+        assert_eq!(analysis.structure, "2/2 Z twill");
+        let analysis = analyse(&draft(4, 16, &lifts, 16), false);
+        assert_eq!(analysis.structure, "2/2 S twill");
+    }
+
+    #[test]
+    fn satin() {
+        let lifts = [bit(1), bit(3), bit(5), bit(2), bit(4)];
+        let analysis = analyse(&draft(5, 20, &lifts, 20), true);
+        assert_eq!(analysis.structure, "5-end satin");
+        assert_eq!(analysis.facing(), "weft-faced");
+    }
+}
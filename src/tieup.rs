@@ -0,0 +1,242 @@
+//! Turning a liftplan into a tie-up and treadling for a floor loom.
+
+use std::collections::HashMap;
+
+use crate::draft::{bit, Mask};
+
+/// How many search steps to spend, across every tie-up size tried, before
+/// settling for a greedy answer.
+const SEARCH_BUDGET: u32 = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TieupSynthesis {
+    /// The liftplan this was built from, so it can be discarded if the draft changes.
+    pub liftplan: Vec<Mask>,
+    /// Shafts tied to each treadle, treadle 1 first.
+    pub tieup: Vec<Mask>,
+    /// Treadles pressed on each pick, pick 1 first. At most two are ever pressed at once.
+    pub treadling: Vec<Mask>,
+    /// Picks whose shed can't be made with the tie-up.
+    pub unreproducible: Vec<u32>,
+    /// Whether no tie-up could have fewer treadles. The search gives up on
+    /// hard liftplans, so otherwise this is only the best it found.
+    pub fewest: bool,
+}
+
+impl TieupSynthesis {
+    pub fn treadles(&self) -> u32 {
+        self.tieup.len() as u32
+    }
+
+    pub fn treadling(&self, pick: u32) -> Mask {
+        pick.checked_sub(1)
+            .and_then(|i| self.treadling.get(i as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Looks for the smallest tie-up it can whose treadles, pressed alone or two
+/// at a time (a skeleton tie-up), reproduce every shed in `liftplan`.
+///
+/// If that needs more than `max_treadles`, the most frequently used sheds are
+/// kept and the picks that can't be woven are reported instead.
+pub fn synthesize(liftplan: &[Mask], max_treadles: u32) -> TieupSynthesis {
+    let mut uses: HashMap<Mask, usize> = HashMap::new();
+    for &shed in liftplan.iter().filter(|&&shed| shed != 0) {
+        *uses.entry(shed).or_default() += 1;
+    }
+    let mut sheds: Vec<Mask> = uses.keys().copied().collect();
+    // Most used first, so a partial tie-up keeps the sheds that matter most.
+    sheds.sort_by_key(|shed| (std::cmp::Reverse(uses[shed]), *shed));
+
+    let max_treadles = max_treadles.min(crate::draft::MAX_SHAFTS) as usize;
+    // n treadles can make at most n + n(n-1)/2 distinct sheds.
+    let smallest = (1..)
+        .find(|&n: &usize| n + n * (n - 1) / 2 >= sheds.len())
+        .unwrap_or(1);
+    let mut budget = SEARCH_BUDGET;
+    let tieup = (smallest..=max_treadles.min(sheds.len()))
+        .find_map(|size| {
+            let mut tieup = vec![];
+            search(&sheds, &mut tieup, size, &mut budget).then_some(tieup)
+        })
+        .unwrap_or_else(|| greedy(&sheds, max_treadles));
+
+    let mut unreproducible = vec![];
+    let treadling = liftplan
+        .iter()
+        .zip(1..)
+        .map(|(&shed, pick)| {
+            if shed == 0 {
+                return 0;
+            }
+            press_for(&tieup, shed).unwrap_or_else(|| {
+                unreproducible.push(pick);
+                0
+            })
+        })
+        .collect();
+
+    TieupSynthesis {
+        liftplan: liftplan.to_vec(),
+        fewest: unreproducible.is_empty() && tieup.len() <= smallest,
+        tieup,
+        treadling,
+        unreproducible,
+    }
+}
+
+/// The treadles to press together to lift exactly `shed`, if any.
+fn press_for(tieup: &[Mask], shed: Mask) -> Option<Mask> {
+    let treadles: Vec<_> = (1..).zip(tieup).collect();
+    treadles.iter().find_map(|&(a, &first)| {
+        if first == shed {
+            return Some(bit(a));
+        }
+        treadles
+            .iter()
+            .filter(|&&(b, _)| b > a)
+            .find(|&&(_, &second)| first | second == shed)
+            .map(|&(b, _)| bit(a) | bit(b))
+    })
+}
+
+/// Depth-first search for a tie-up of exactly `size` treadles that covers every shed.
+///
+/// We always branch on the first shed we can't make yet, and only consider
+/// treadles built from it: the whole shed, the part an existing treadle is
+/// missing, or a split along where it overlaps another shed.
+fn search(sheds: &[Mask], tieup: &mut Vec<Mask>, size: usize, budget: &mut u32) -> bool {
+    if *budget == 0 {
+        return false;
+    }
+    *budget -= 1;
+
+    let Some(&shed) = sheds.iter().find(|&&shed| press_for(tieup, shed).is_none()) else {
+        return true;
+    };
+    let spare = size - tieup.len();
+    if spare == 0 {
+        return false;
+    }
+
+    let mut options: Vec<Vec<Mask>> = vec![];
+    for &treadle in tieup.iter() {
+        if treadle & !shed == 0 {
+            options.push(vec![shed & !treadle]);
+        }
+    }
+    if spare >= 2 {
+        for &other in sheds {
+            let part = shed & other;
+            if part != 0 && part != shed {
+                options.push(vec![part, shed & !part]);
+            }
+        }
+    }
+    options.push(vec![shed]);
+    options.dedup();
+
+    for option in options {
+        if option.len() > spare {
+            continue;
+        }
+        let len = tieup.len();
+        tieup.extend(option);
+        if search(sheds, tieup, size, budget) {
+            return true;
+        }
+        tieup.truncate(len);
+    }
+    false
+}
+
+/// Adds treadles for the most used sheds until we run out of treadles.
+fn greedy(sheds: &[Mask], max_treadles: usize) -> Vec<Mask> {
+    let mut tieup: Vec<Mask> = vec![];
+    for &shed in sheds {
+        if tieup.len() >= max_treadles || press_for(&tieup, shed).is_some() {
+            continue;
+        }
+        let missing = tieup
+            .iter()
+            .filter(|&&treadle| treadle & !shed == 0)
+            .map(|&treadle| shed & !treadle)
+            .min_by_key(|missing| missing.count_ones())
+            .unwrap_or(shed);
+        tieup.push(missing);
+    }
+    tieup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the treadling presses at most two treadles and lifts the
+    /// liftplan's shed on every pick.
+    fn reproduces(synthesis: &TieupSynthesis) -> bool {
+        (1..).zip(&synthesis.liftplan).all(|(pick, &shed)| {
+            let pressed = synthesis.treadling(pick);
+            let lifted = (1..)
+                .zip(&synthesis.tieup)
+                .filter(|&(treadle, _)| pressed & bit(treadle) != 0)
+                .fold(0, |lifted, (_, &tied)| lifted | tied);
+            pressed.count_ones() <= 2 && lifted == shed
+        })
+    }
+
+    #[test]
+    fn plain_weave() {
+        let synthesis = synthesize(&[bit(1) | bit(3), bit(2) | bit(4)], 10);
+        assert_eq!(synthesis.treadles(), 2);
+        assert!(synthesis.fewest);
+        assert!(synthesis.unreproducible.is_empty());
+        assert!(reproduces(&synthesis));
+    }
+
+    #[test]
+    fn skeleton_tieup() {
+        // The third shed is the first two together, so two treadles do.
+        let liftplan = [
+            bit(1) | bit(2),
+            bit(3) | bit(4),
+            bit(1) | bit(2) | bit(3) | bit(4),
+        ];
+        let synthesis = synthesize(&liftplan, 10);
+        assert_eq!(synthesis.treadles(), 2);
+        assert!(synthesis.fewest);
+        assert!(reproduces(&synthesis));
+    }
+
+    #[test]
+    fn twill() {
+        let liftplan = [
+            bit(1) | bit(2),
+            bit(2) | bit(3),
+            bit(3) | bit(4),
+            bit(4) | bit(1),
+        ];
+        let synthesis = synthesize(&liftplan, 10);
+        assert!(synthesis.treadles() <= 4);
+        assert!(synthesis.unreproducible.is_empty());
+        assert!(reproduces(&synthesis));
+    }
+
+    #[test]
+    fn too_few_treadles() {
+        let liftplan = [bit(1), bit(2), bit(3), bit(4), bit(5), bit(6)];
+        let synthesis = synthesize(&liftplan, 4);
+        assert_eq!(synthesis.treadles(), 4);
+        assert_eq!(synthesis.unreproducible, [5, 6]);
+        assert!(!synthesis.fewest);
+    }
+
+    #[test]
+    fn empty_picks_need_no_treadles() {
+        let synthesis = synthesize(&[0, bit(1), 0], 4);
+        assert_eq!(synthesis.treadling, [0, bit(1), 0]);
+        assert!(synthesis.unreproducible.is_empty());
+    }
+}
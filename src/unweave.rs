@@ -0,0 +1,35 @@
+//! Taking weaving back out to a mistake.
+//!
+//! Picks are counted through the whole piece. The current pick is the next
+//! to weave, so the last one woven is the one before it.
+
+/// The last pick woven, if any, when `current` is next to weave.
+pub fn last_woven(current: u32) -> Option<u32> {
+    current.checked_sub(1).filter(|&pick| pick > 0)
+}
+
+/// Picks to take out, down to and including `mistake`, when `current` is
+/// next to weave.
+pub fn picks_to_unweave(current: u32, mistake: u32) -> u32 {
+    current.saturating_sub(mistake)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_woven_yet() {
+        assert_eq!(last_woven(1), None);
+        assert_eq!(last_woven(2), Some(1));
+    }
+
+    #[test]
+    fn counting_from_the_last_woven_pick() {
+        // Picks 1 to 9 are woven and 7 is wrong: 9, 8 and 7 come out.
+        assert_eq!(picks_to_unweave(10, 7), 3);
+        // Marking the last woven pick takes out just that one.
+        assert_eq!(picks_to_unweave(10, last_woven(10).unwrap()), 1);
+        assert_eq!(picks_to_unweave(7, 7), 0);
+    }
+}
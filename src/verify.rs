@@ -0,0 +1,97 @@
+//! Checking the threading on the loom against the draft.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::draft::{Draft, Mask};
+
+/// The shafts each end has been found on so far.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Verification {
+    /// The threading being checked against, so that opening a different
+    /// draft starts afresh.
+    expected: Vec<Mask>,
+    found: BTreeMap<u32, Mask>,
+}
+
+/// An end that isn't on the shafts the draft says it should be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Correction {
+    pub end: u32,
+    pub expected: Mask,
+    pub found: Mask,
+}
+
+impl Verification {
+    pub fn new(draft: &Draft) -> Self {
+        Self {
+            expected: (1..=draft.ends()).map(|end| draft.threading(end)).collect(),
+            found: BTreeMap::new(),
+        }
+    }
+
+    /// Whether this was made against `draft`'s threading.
+    pub fn is_for(&self, draft: &Draft) -> bool {
+        self.expected.len() == draft.ends() as usize
+            && (1..)
+                .zip(&self.expected)
+                .all(|(end, &mask)| draft.threading(end) == mask)
+    }
+
+    pub fn expected(&self, end: u32) -> Mask {
+        end.checked_sub(1)
+            .and_then(|i| self.expected.get(i as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The shafts `end` was found on, if it has been checked.
+    pub fn found(&self, end: u32) -> Option<Mask> {
+        self.found.get(&end).copied()
+    }
+
+    /// The shafts `end` is on as far as we know: where it was found, or
+    /// where the draft says until it's checked.
+    pub fn actual(&self, end: u32) -> Mask {
+        self.found(end).unwrap_or_else(|| self.expected(end))
+    }
+
+    pub fn record(&mut self, end: u32, found: Mask) {
+        self.found.insert(end, found);
+    }
+
+    /// Marks `end` as threaded as drafted, unless it's already been checked.
+    pub fn confirm(&mut self, end: u32) {
+        let expected = self.expected(end);
+        self.found.entry(end).or_insert(expected);
+    }
+
+    pub fn checked(&self) -> usize {
+        self.found.len()
+    }
+
+    pub fn corrections(&self) -> Vec<Correction> {
+        self.found
+            .iter()
+            .map(|(&end, &found)| Correction {
+                end,
+                expected: self.expected(end),
+                found,
+            })
+            .filter(|correction| correction.found != correction.expected)
+            .collect()
+    }
+
+    /// The picks on which a threading mistake on `end` puts it on the wrong
+    /// side of the weft.
+    pub fn spoilt_picks(&self, draft: &Draft, end: u32) -> Vec<u32> {
+        let (expected, actual) = (self.expected(end), self.actual(end));
+        (1..)
+            .zip(draft.liftplan())
+            .filter(|&(_, &lifted)| (lifted & expected != 0) != (lifted & actual != 0))
+            .map(|(pick, _)| pick)
+            .collect()
+    }
+}
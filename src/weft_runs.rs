@@ -0,0 +1,85 @@
+//! Runs of the same weft colour, for winding bobbins ahead of time.
+
+use crate::draft::Draft;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub colour: [u8; 3],
+    pub first_pick: u32,
+    pub picks: u32,
+}
+
+impl Run {
+    pub fn contains(&self, pick: u32) -> bool {
+        (self.first_pick..self.first_pick + self.picks).contains(&pick)
+    }
+}
+
+/// The draft's weft split into runs of one colour.
+pub fn runs(draft: &Draft) -> Vec<Run> {
+    let mut runs: Vec<Run> = vec![];
+    for pick in 1..=draft.picks() {
+        let colour = draft.weft_colour(pick);
+        match runs.last_mut() {
+            Some(run) if run.colour == colour => run.picks += 1,
+            _ => runs.push(Run {
+                colour,
+                first_pick: pick,
+                picks: 1,
+            }),
+        }
+    }
+    runs
+}
+
+/// How many picks of the run containing `pick` are left, including this
+/// one, and the run after it. After the last run the treadling starts again
+/// from the first, so if they're the same colour the last run carries on
+/// into the first.
+pub fn position(runs: &[Run], pick: u32) -> Option<(u32, Run)> {
+    let index = runs.iter().position(|run| run.contains(pick))?;
+    let last = runs.len() - 1;
+    let wraps = last > 0 && runs[0].colour == runs[last].colour;
+    let run = &runs[index];
+    let mut left = run.first_pick + run.picks - pick;
+    let mut next_index = (index + 1) % runs.len();
+    if wraps && index == last {
+        left += runs[0].picks;
+        next_index = 1;
+    }
+    let mut next = runs[next_index];
+    if wraps && next_index == last {
+        next.picks += runs[0].picks;
+    }
+    Some((left, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(colour: u8, first_pick: u32, picks: u32) -> Run {
+        Run {
+            colour: [colour; 3],
+            first_pick,
+            picks,
+        }
+    }
+
+    #[test]
+    fn next_run() {
+        let runs = [run(1, 1, 2), run(2, 3, 3), run(3, 6, 1)];
+        assert_eq!(position(&runs, 4), Some((2, runs[2])));
+        assert_eq!(position(&runs, 6), Some((1, runs[0])));
+        assert_eq!(position(&runs, 7), None);
+    }
+
+    #[test]
+    fn last_run_carries_on_into_the_first() {
+        let runs = [run(1, 1, 2), run(2, 3, 3), run(1, 6, 2)];
+        assert_eq!(position(&runs, 6), Some((4, runs[1])));
+        assert_eq!(position(&runs, 1), Some((2, runs[1])));
+        // Coming up to the last run, it lasts into the first.
+        assert_eq!(position(&runs, 3), Some((3, run(1, 6, 4))));
+    }
+}
@@ -0,0 +1,249 @@
+//! The weaver's yarn stash, and how far it goes.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{calculator::YarnNeeds, draft::Draft};
+
+const INCHES_PER_YARD: f32 = 36.;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Yarn {
+    pub name: String,
+    pub fibre: String,
+    pub yards_per_pound: f32,
+    pub colour: [u8; 3],
+    /// How much is on hand, in pounds.
+    pub on_hand: f32,
+}
+
+impl Default for Yarn {
+    fn default() -> Self {
+        Self {
+            name: "New yarn".to_string(),
+            fibre: String::new(),
+            yards_per_pound: 840.,
+            colour: [255, 255, 255],
+            on_hand: 1.,
+        }
+    }
+}
+
+impl Yarn {
+    pub fn yards_on_hand(&self) -> f32 {
+        self.on_hand * self.yards_per_pound
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct YarnStash {
+    pub yarns: Vec<Yarn>,
+    /// Which yarn each colour table entry is woven in, by index into
+    /// `yarns`, for each draft by its key. Colourways keep each thread's
+    /// entry, so a yarn stays with its entry whatever colour it's given.
+    pub assignments: BTreeMap<String, BTreeMap<u32, usize>>,
+}
+
+impl YarnStash {
+    pub fn yarn_for(&self, draft: &str, entry: u32) -> Option<usize> {
+        self.assignments
+            .get(draft)
+            .and_then(|assignments| assignments.get(&entry))
+            .copied()
+            .filter(|&yarn| yarn < self.yarns.len())
+    }
+
+    pub fn assign(&mut self, draft: &str, entry: u32, yarn: Option<usize>) {
+        let assignments = self.assignments.entry(draft.to_string()).or_default();
+        match yarn {
+            Some(yarn) => assignments.insert(entry, yarn),
+            None => assignments.remove(&entry),
+        };
+    }
+
+    /// Removes a yarn, along with any colours assigned to it.
+    pub fn remove(&mut self, yarn: usize) {
+        self.yarns.remove(yarn);
+        for assignments in self.assignments.values_mut() {
+            assignments.retain(|_, assigned| *assigned != yarn);
+            for assigned in assignments.values_mut() {
+                if *assigned > yarn {
+                    *assigned -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// How much of one yarn the project uses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YarnUsage {
+    pub yarn: usize,
+    /// Yards wound into the warp.
+    pub warp: f32,
+    /// Yards of weft woven so far.
+    pub woven: f32,
+    /// Yards of weft for the whole piece.
+    pub weft: f32,
+    /// The pick the yarn runs out on, if it does: 0 if there isn't enough
+    /// for the warp.
+    pub runs_out_at: Option<u32>,
+}
+
+impl YarnUsage {
+    pub fn used(&self) -> f32 {
+        self.warp + self.woven
+    }
+
+    pub fn total(&self) -> f32 {
+        self.warp + self.weft
+    }
+}
+
+/// Works out how much of each assigned yarn goes into the warp and weft of
+/// the project in `needs`, with `picks_woven` picks woven so far. `key` says
+/// which draft's assignments to go by.
+pub fn usage(
+    stash: &YarnStash,
+    key: &str,
+    draft: &Draft,
+    needs: &YarnNeeds,
+    picks_woven: u32,
+) -> Vec<YarnUsage> {
+    let repeat = draft.picks().max(1);
+    let pick_yards = needs.pick_length / INCHES_PER_YARD;
+    (0..stash.yarns.len())
+        .filter_map(|yarn| {
+            let uses = |entry: u32| stash.yarn_for(key, entry) == Some(yarn);
+            let warp: f32 = needs
+                .warp
+                .iter()
+                .filter(|need| uses(need.entry))
+                .map(|need| need.yards)
+                .sum();
+            let weft: f32 = needs
+                .weft
+                .iter()
+                .filter(|need| uses(need.entry))
+                .map(|need| need.yards)
+                .sum();
+            if warp == 0. && weft == 0. {
+                return None;
+            }
+
+            let available = stash.yarns[yarn].yards_on_hand() - warp;
+            let mut runs_out_at = (available < 0.).then_some(0);
+            let mut woven = 0.;
+            let mut weft_so_far = 0.;
+            for pick in 1..=needs.picks {
+                if uses(draft.weft_entry((pick - 1) % repeat + 1)) {
+                    weft_so_far += pick_yards;
+                }
+                if pick == picks_woven {
+                    woven = weft_so_far;
+                }
+                if runs_out_at.is_none() && weft_so_far > available {
+                    runs_out_at = Some(pick);
+                }
+            }
+            if picks_woven > needs.picks {
+                woven = weft_so_far;
+            }
+            Some(YarnUsage {
+                yarn,
+                warp,
+                woven,
+                weft,
+                runs_out_at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        calculator::{estimate, Project},
+        draft::bit,
+    };
+
+    fn stash() -> YarnStash {
+        let mut stash = YarnStash {
+            yarns: vec![Yarn::default(), Yarn::default(), Yarn::default()],
+            ..YarnStash::default()
+        };
+        stash.assign("a.wif", 1, Some(0));
+        stash.assign("a.wif", 2, Some(2));
+        stash.assign("b.wif", 1, Some(1));
+        stash
+    }
+
+    #[test]
+    fn assigns_per_draft() {
+        let mut stash = stash();
+        assert_eq!(stash.yarn_for("a.wif", 1), Some(0));
+        assert_eq!(stash.yarn_for("b.wif", 1), Some(1));
+        assert_eq!(stash.yarn_for("b.wif", 2), None);
+
+        stash.remove(1);
+        assert_eq!(stash.yarn_for("b.wif", 1), None);
+        assert_eq!(stash.yarn_for("a.wif", 2), Some(1));
+
+        stash.assign("a.wif", 2, None);
+        assert_eq!(stash.yarn_for("a.wif", 2), None);
+    }
+
+    #[test]
+    fn removing_an_assigned_yarn() {
+        let draft = Draft::for_test(2, &[bit(1), bit(2)], &[bit(1), bit(2)]).recoloured(
+            BTreeMap::from([(1, [0; 3]), (2, [255; 3])]),
+            |end, _| end,
+            |pick, _| pick,
+        );
+        let needs = estimate(&draft, &Project::default(), 10., 10.);
+        let mut stash = stash();
+        let yarns = |stash: &YarnStash| -> Vec<usize> {
+            usage(stash, "a.wif", &draft, &needs, 0)
+                .iter()
+                .map(|usage| usage.yarn)
+                .collect()
+        };
+        assert_eq!(yarns(&stash), [0, 2]);
+        // The last yarn, in use for entry 2.
+        stash.remove(2);
+        assert_eq!(yarns(&stash), [0]);
+        stash.remove(0);
+        assert!(yarns(&stash).is_empty());
+    }
+
+    #[test]
+    fn runs_out() {
+        let draft = Draft::for_test(2, &[bit(1), bit(2)], &[bit(1), bit(2)]).recoloured(
+            BTreeMap::from([(1, [0; 3]), (2, [255; 3])]),
+            |end, _| end,
+            |pick, _| pick,
+        );
+        let project = Project {
+            finished_length: 36.,
+            loom_waste: 0.,
+            take_up: 0.,
+            shrinkage: 0.,
+            ..Project::default()
+        };
+        let needs = estimate(&draft, &project, 1., 1.);
+        let mut stash = stash();
+        // Enough for the warp and four picks of entry 1, at 1/18 yd a pick.
+        stash.yarns[0].on_hand = 1.25 / stash.yarns[0].yards_per_pound;
+        let usage = usage(&stash, "a.wif", &draft, &needs, 0);
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].yarn, 0);
+        assert_eq!(usage[0].warp, 1.);
+        assert_eq!(usage[0].runs_out_at, Some(9));
+        assert_eq!(usage[1].yarn, 2);
+        assert_eq!(usage[1].runs_out_at, None);
+    }
+}